@@ -1,6 +1,3 @@
-#[path = "./common/macros.rs"]
-mod macros;
-
 use std::{hint::black_box, time::Duration};
 
 use criterion::{BenchmarkId, Criterion, Throughput};
@@ -1,11 +1,7 @@
-#[path = "./common/macros.rs"]
-mod macros;
-
 use std::{fs, hint::black_box};
 
-use criterion::Criterion;
+use criterion::{Criterion, Throughput};
 
-use image;
 use pico_detect::{Detector, Square};
 
 pub fn bench_load(c: &mut Criterion) {
@@ -26,3 +22,33 @@ pub fn bench_inference(c: &mut Criterion) {
         b.iter(|| detector.classify(black_box(&image), black_box(s)));
     });
 }
+
+pub fn bench_batch(c: &mut Criterion) {
+    let image = load_test_image!();
+    let detector = load_model!(facefinder);
+
+    // a row of windows of the same size across the image
+    let size = 100;
+    let regions: Vec<Square> = (0..image.width() - size)
+        .step_by(4)
+        .map(|x| Square::new(x as i32, 200, size))
+        .collect();
+    let mut scores = vec![None; regions.len()];
+
+    let mut group = c.benchmark_group("Detector::classify[row]");
+    group.throughput(Throughput::Elements(regions.len() as u64));
+
+    group.bench_function("single", |b| {
+        b.iter(|| {
+            for (region, score) in regions.iter().zip(scores.iter_mut()) {
+                *score = detector.classify(black_box(&image), *region);
+            }
+        });
+    });
+
+    group.bench_function("batch", |b| {
+        b.iter(|| detector.classify_batch(black_box(&image), black_box(&regions), &mut scores));
+    });
+
+    group.finish();
+}
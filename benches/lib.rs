@@ -5,6 +5,9 @@ extern crate criterion;
 
 use criterion::Criterion;
 
+#[path = "./common/macros.rs"]
+mod macros;
+
 mod clusterizer;
 mod detector;
 mod localizer;
@@ -33,6 +36,7 @@ criterion_group!(
         .measurement_time(Duration::from_secs(20));
     targets =
         detector::bench_inference,
+        detector::bench_batch,
         localizer::bench_inference,
         shaper::bench_inference,
 );
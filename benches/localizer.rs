@@ -1,11 +1,7 @@
-#[path = "./common/macros.rs"]
-mod macros;
-
 use std::{fs, hint::black_box};
 
 use criterion::Criterion;
 
-use image;
 use pico_detect::{Localizer, Square};
 
 pub fn bench_load(c: &mut Criterion) {
@@ -1,6 +1,3 @@
-#[path = "./common/macros.rs"]
-mod macros;
-
 use std::{fmt::Display, hint::black_box};
 
 use criterion::{BenchmarkId, Criterion, Throughput};
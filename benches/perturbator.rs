@@ -1,6 +1,3 @@
-#[path = "./common/macros.rs"]
-mod macros;
-
 use std::hint::black_box;
 
 use criterion::{BenchmarkId, Criterion, Throughput};
@@ -1,11 +1,7 @@
-#[path = "./common/macros.rs"]
-mod macros;
-
 use std::{fs, hint::black_box};
 
 use criterion::Criterion;
 
-use image;
 use pico_detect::{Shaper, Square};
 
 pub fn bench_load(c: &mut Criterion) {
@@ -20,7 +16,7 @@ pub fn bench_inference(c: &mut Criterion) {
     let image = load_test_image!();
     let shaper = load_model!(shaper);
 
-    let r = Square::new(213, 225, 153).into();
+    let r = Square::new(213, 225, 153);
 
     c.bench_function("Shaper::shape[inference]", |b| {
         b.iter(|| shaper.shape(black_box(&image), black_box(r)));
@@ -0,0 +1,188 @@
+/* pico-detect C API, generated with cbindgen: do not edit manually. */
+
+#ifndef PICO_DETECT_H
+#define PICO_DETECT_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Opaque object detector handle.
+typedef struct PicoDetector PicoDetector;
+
+// Opaque object localizer handle.
+typedef struct PicoLocalizer PicoLocalizer;
+
+// Opaque landmarks shaper handle.
+typedef struct PicoShaper PicoShaper;
+
+// Multiscale detection parameters.
+typedef struct PicoDetectOptions {
+  // Minimum window size.
+  uint32_t min_size;
+  // Maximum window size, `0` stands for the smallest image dimension.
+  uint32_t max_size;
+  // Window shift relative to its size in `(0, 1]` range.
+  float shift_factor;
+  // Window scale step, finite and greater than `1`.
+  float scale_factor;
+  // Intersection over union threshold of clustering.
+  float intersection_threshold;
+  // Minimum score of clustered detections.
+  float score_threshold;
+} PicoDetectOptions;
+
+// Grayscale image buffer.
+typedef struct PicoImage {
+  // Pointer to the first pixel.
+  const uint8_t *data;
+  // Image width in pixels.
+  uint32_t width;
+  // Image height in pixels.
+  uint32_t height;
+  // Distance between rows in bytes, at least `width`.
+  size_t stride;
+} PicoImage;
+
+// Detected object region with its score.
+typedef struct PicoDetection {
+  // Center `x` coordinate.
+  float x;
+  // Center `y` coordinate.
+  float y;
+  // Region size.
+  float size;
+  // Detection score.
+  float score;
+} PicoDetection;
+
+// Point on the image.
+typedef struct PicoPoint {
+  float x;
+  float y;
+} PicoPoint;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Returns default multiscale detection parameters.
+struct PicoDetectOptions pico_detect_options_default(void);
+
+// Load a detector from the model bytes, returns `NULL` on failure.
+//
+// # Safety
+//
+// `data` should point to `len` readable bytes.
+struct PicoDetector *pico_detector_load(const uint8_t *data, size_t len);
+
+// Release a detector, `NULL` is ignored.
+//
+// # Safety
+//
+// `detector` should be returned by [`pico_detector_load`] and not released before.
+void pico_detector_free(struct PicoDetector *detector);
+
+// Classify a square region with the top-left corner at `(left, top)`.
+//
+// Returns `1` and writes `score` (if not `NULL`) when the region is an object,
+// `0` when it is not.
+//
+// # Safety
+//
+// `detector` and `image` should be valid pointers, `image` should describe a valid buffer.
+int pico_detector_classify(const struct PicoDetector *detector,
+                           const struct PicoImage *image,
+                           int32_t left,
+                           int32_t top,
+                           uint32_t size,
+                           float *score);
+
+// Run multiscale detection with clustering, `options` may be `NULL` for defaults.
+//
+// Writes at most `capacity` detections into `out` and returns the number
+// of all detections found.
+//
+// # Safety
+//
+// `detector` and `image` should be valid pointers, `image` should describe a valid buffer,
+// `out` should point to `capacity` writable items.
+ptrdiff_t pico_detector_detect(const struct PicoDetector *detector,
+                               const struct PicoImage *image,
+                               const struct PicoDetectOptions *options,
+                               struct PicoDetection *out,
+                               size_t capacity);
+
+// Load a localizer from the model bytes, returns `NULL` on failure.
+//
+// # Safety
+//
+// `data` should point to `len` readable bytes.
+struct PicoLocalizer *pico_localizer_load(const uint8_t *data, size_t len);
+
+// Release a localizer, `NULL` is ignored.
+//
+// # Safety
+//
+// `localizer` should be returned by [`pico_localizer_load`] and not released before.
+void pico_localizer_free(struct PicoLocalizer *localizer);
+
+// Localize an object inside the region and write its position into `out`.
+//
+// Returns `0` on success.
+//
+// # Safety
+//
+// `localizer`, `image` and `out` should be valid pointers,
+// `image` should describe a valid buffer.
+int pico_localizer_localize(const struct PicoLocalizer *localizer,
+                            const struct PicoImage *image,
+                            float x,
+                            float y,
+                            float size,
+                            struct PicoPoint *out);
+
+// Load a shaper from the model bytes, returns `NULL` on failure.
+//
+// # Safety
+//
+// `data` should point to `len` readable bytes.
+struct PicoShaper *pico_shaper_load(const uint8_t *data, size_t len);
+
+// Release a shaper, `NULL` is ignored.
+//
+// # Safety
+//
+// `shaper` should be returned by [`pico_shaper_load`] and not released before.
+void pico_shaper_free(struct PicoShaper *shaper);
+
+// Returns the number of landmarks estimated by the shaper.
+//
+// # Safety
+//
+// `shaper` should be a valid pointer.
+size_t pico_shaper_size(const struct PicoShaper *shaper);
+
+// Estimate landmarks inside the region.
+//
+// Writes at most `capacity` points into `out` and returns the number of landmarks.
+//
+// # Safety
+//
+// `shaper` and `image` should be valid pointers, `image` should describe a valid buffer,
+// `out` should point to `capacity` writable items.
+ptrdiff_t pico_shaper_shape(const struct PicoShaper *shaper,
+                            const struct PicoImage *image,
+                            float x,
+                            float y,
+                            float size,
+                            struct PicoPoint *out,
+                            size_t capacity);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* PICO_DETECT_H */
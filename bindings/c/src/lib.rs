@@ -0,0 +1,386 @@
+//! C API of [`pico_detect`].
+//!
+//! Models are opaque handles created with `pico_*_load` and released with
+//! `pico_*_free`. Images are 8-bit grayscale buffers with a row stride in bytes.
+//! Regions are passed as center coordinates and size, the same way as [`Target`].
+//!
+//! Functions returning a count or a status return a negative value on invalid arguments.
+
+use std::os::raw::c_int;
+use std::ptr;
+use std::slice;
+
+use pico_detect::clusterize::Clusterizer;
+use pico_detect::multiscale::Multiscaler;
+use pico_detect::{
+    DetectMultiscale, Detector, GrayView, Localizer, Rectangle, Shaper, Square, Target,
+};
+
+/// Grayscale image buffer.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct PicoImage {
+    /// Pointer to the first pixel.
+    pub data: *const u8,
+    /// Image width in pixels.
+    pub width: u32,
+    /// Image height in pixels.
+    pub height: u32,
+    /// Distance between rows in bytes, at least `width`.
+    pub stride: usize,
+}
+
+/// Point on the image.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PicoPoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Detected object region with its score.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PicoDetection {
+    /// Center `x` coordinate.
+    pub x: f32,
+    /// Center `y` coordinate.
+    pub y: f32,
+    /// Region size.
+    pub size: f32,
+    /// Detection score.
+    pub score: f32,
+}
+
+/// Multiscale detection parameters.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PicoDetectOptions {
+    /// Minimum window size.
+    pub min_size: u32,
+    /// Maximum window size, `0` stands for the smallest image dimension.
+    pub max_size: u32,
+    /// Window shift relative to its size in `(0, 1]` range.
+    pub shift_factor: f32,
+    /// Window scale step, finite and greater than `1`.
+    pub scale_factor: f32,
+    /// Intersection over union threshold of clustering.
+    pub intersection_threshold: f32,
+    /// Minimum score of clustered detections.
+    pub score_threshold: f32,
+}
+
+impl Default for PicoDetectOptions {
+    #[inline]
+    fn default() -> Self {
+        let clusterizer = Clusterizer::default();
+
+        Self {
+            min_size: 100,
+            max_size: 0,
+            shift_factor: 0.05,
+            scale_factor: 1.1,
+            intersection_threshold: clusterizer.intersection_threshold,
+            score_threshold: clusterizer.score_threshold,
+        }
+    }
+}
+
+/// Opaque object detector handle.
+pub struct PicoDetector(Detector);
+
+/// Opaque object localizer handle.
+pub struct PicoLocalizer(Localizer);
+
+/// Opaque landmarks shaper handle.
+pub struct PicoShaper(Shaper);
+
+unsafe fn image_view<'a>(image: *const PicoImage) -> Option<GrayView<'a>> {
+    let image = image.as_ref()?;
+
+    if image.data.is_null() || image.width == 0 || image.height == 0 {
+        return None;
+    }
+
+    if image.stride < image.width as usize {
+        return None;
+    }
+
+    let len = image
+        .stride
+        .checked_mul(image.height as usize - 1)?
+        .checked_add(image.width as usize)?;
+
+    GrayView::new(
+        slice::from_raw_parts(image.data, len),
+        image.width,
+        image.height,
+        image.stride,
+    )
+    .ok()
+}
+
+unsafe fn model<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    if data.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(data, len))
+    }
+}
+
+fn boxed<T>(value: Option<T>) -> *mut T {
+    value.map_or(ptr::null_mut(), |value| Box::into_raw(Box::new(value)))
+}
+
+/// Returns default multiscale detection parameters.
+#[no_mangle]
+pub extern "C" fn pico_detect_options_default() -> PicoDetectOptions {
+    PicoDetectOptions::default()
+}
+
+/// Load a detector from the model bytes, returns `NULL` on failure.
+///
+/// # Safety
+///
+/// `data` should point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn pico_detector_load(data: *const u8, len: usize) -> *mut PicoDetector {
+    boxed(model(data, len).and_then(|data| Detector::load(data).ok().map(PicoDetector)))
+}
+
+/// Release a detector, `NULL` is ignored.
+///
+/// # Safety
+///
+/// `detector` should be returned by [`pico_detector_load`] and not released before.
+#[no_mangle]
+pub unsafe extern "C" fn pico_detector_free(detector: *mut PicoDetector) {
+    if !detector.is_null() {
+        drop(Box::from_raw(detector));
+    }
+}
+
+/// Classify a square region with the top-left corner at `(left, top)`.
+///
+/// Returns `1` and writes `score` (if not `NULL`) when the region is an object,
+/// `0` when it is not.
+///
+/// # Safety
+///
+/// `detector` and `image` should be valid pointers, `image` should describe a valid buffer.
+#[no_mangle]
+pub unsafe extern "C" fn pico_detector_classify(
+    detector: *const PicoDetector,
+    image: *const PicoImage,
+    left: i32,
+    top: i32,
+    size: u32,
+    score: *mut f32,
+) -> c_int {
+    let (Some(detector), Some(image)) = (detector.as_ref(), image_view(image)) else {
+        return -1;
+    };
+
+    match detector.0.classify(&image, Square::new(left, top, size)) {
+        Some(value) => {
+            if let Some(score) = score.as_mut() {
+                *score = value;
+            }
+            1
+        }
+        None => 0,
+    }
+}
+
+/// Run multiscale detection with clustering, `options` may be `NULL` for defaults.
+///
+/// Writes at most `capacity` detections into `out` and returns the number
+/// of all detections found.
+///
+/// # Safety
+///
+/// `detector` and `image` should be valid pointers, `image` should describe a valid buffer,
+/// `out` should point to `capacity` writable items.
+#[no_mangle]
+pub unsafe extern "C" fn pico_detector_detect(
+    detector: *const PicoDetector,
+    image: *const PicoImage,
+    options: *const PicoDetectOptions,
+    out: *mut PicoDetection,
+    capacity: usize,
+) -> isize {
+    let (Some(detector), Some(image)) = (detector.as_ref(), image_view(image)) else {
+        return -1;
+    };
+
+    if out.is_null() && capacity > 0 {
+        return -1;
+    }
+
+    let options = options.as_ref().copied().unwrap_or_default();
+    let max_size = match options.max_size {
+        0 => image.width().min(image.height()),
+        value => value,
+    };
+
+    let Ok(multiscaler) = Multiscaler::new(
+        options.min_size,
+        max_size,
+        options.shift_factor,
+        options.scale_factor,
+    ) else {
+        return -1;
+    };
+
+    let clusterizer = Clusterizer::default()
+        .intersection_threshold(options.intersection_threshold)
+        .score_threshold(options.score_threshold);
+
+    let Ok(detect) = DetectMultiscale::builder()
+        .multiscaler(multiscaler)
+        .clusterizer(clusterizer)
+        .build()
+    else {
+        return -1;
+    };
+
+    let detections = detect.run(&detector.0, &image);
+
+    for (i, detection) in detections.iter().take(capacity).enumerate() {
+        let region = detection.region();
+
+        out.add(i).write(PicoDetection {
+            x: region.x(),
+            y: region.y(),
+            size: region.size(),
+            score: detection.score(),
+        });
+    }
+
+    detections.len() as isize
+}
+
+/// Load a localizer from the model bytes, returns `NULL` on failure.
+///
+/// # Safety
+///
+/// `data` should point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn pico_localizer_load(data: *const u8, len: usize) -> *mut PicoLocalizer {
+    boxed(model(data, len).and_then(|data| Localizer::load(data).ok().map(PicoLocalizer)))
+}
+
+/// Release a localizer, `NULL` is ignored.
+///
+/// # Safety
+///
+/// `localizer` should be returned by [`pico_localizer_load`] and not released before.
+#[no_mangle]
+pub unsafe extern "C" fn pico_localizer_free(localizer: *mut PicoLocalizer) {
+    if !localizer.is_null() {
+        drop(Box::from_raw(localizer));
+    }
+}
+
+/// Localize an object inside the region and write its position into `out`.
+///
+/// Returns `0` on success.
+///
+/// # Safety
+///
+/// `localizer`, `image` and `out` should be valid pointers,
+/// `image` should describe a valid buffer.
+#[no_mangle]
+pub unsafe extern "C" fn pico_localizer_localize(
+    localizer: *const PicoLocalizer,
+    image: *const PicoImage,
+    x: f32,
+    y: f32,
+    size: f32,
+    out: *mut PicoPoint,
+) -> c_int {
+    let (Some(localizer), Some(image), Some(out)) =
+        (localizer.as_ref(), image_view(image), out.as_mut())
+    else {
+        return -1;
+    };
+
+    let point = localizer.0.localize(&image, Target::new(x, y, size));
+    *out = PicoPoint {
+        x: point.x,
+        y: point.y,
+    };
+
+    0
+}
+
+/// Load a shaper from the model bytes, returns `NULL` on failure.
+///
+/// # Safety
+///
+/// `data` should point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn pico_shaper_load(data: *const u8, len: usize) -> *mut PicoShaper {
+    boxed(model(data, len).and_then(|data| Shaper::load(data).ok().map(PicoShaper)))
+}
+
+/// Release a shaper, `NULL` is ignored.
+///
+/// # Safety
+///
+/// `shaper` should be returned by [`pico_shaper_load`] and not released before.
+#[no_mangle]
+pub unsafe extern "C" fn pico_shaper_free(shaper: *mut PicoShaper) {
+    if !shaper.is_null() {
+        drop(Box::from_raw(shaper));
+    }
+}
+
+/// Returns the number of landmarks estimated by the shaper.
+///
+/// # Safety
+///
+/// `shaper` should be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn pico_shaper_size(shaper: *const PicoShaper) -> usize {
+    shaper.as_ref().map_or(0, |shaper| shaper.0.size())
+}
+
+/// Estimate landmarks inside the region.
+///
+/// Writes at most `capacity` points into `out` and returns the number of landmarks.
+///
+/// # Safety
+///
+/// `shaper` and `image` should be valid pointers, `image` should describe a valid buffer,
+/// `out` should point to `capacity` writable items.
+#[no_mangle]
+pub unsafe extern "C" fn pico_shaper_shape(
+    shaper: *const PicoShaper,
+    image: *const PicoImage,
+    x: f32,
+    y: f32,
+    size: f32,
+    out: *mut PicoPoint,
+    capacity: usize,
+) -> isize {
+    let (Some(shaper), Some(image)) = (shaper.as_ref(), image_view(image)) else {
+        return -1;
+    };
+
+    if out.is_null() && capacity > 0 {
+        return -1;
+    }
+
+    let rect = Rectangle::from(Target::new(x, y, size));
+    let points = shaper.0.shape(&image, rect);
+
+    for (i, point) in points.iter().take(capacity).enumerate() {
+        out.add(i).write(PicoPoint {
+            x: point.x,
+            y: point.y,
+        });
+    }
+
+    points.len() as isize
+}
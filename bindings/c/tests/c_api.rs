@@ -0,0 +1,84 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn manifest_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn generate_header() -> String {
+    let config = cbindgen::Config::from_file(manifest_dir().join("cbindgen.toml")).unwrap();
+    let mut header = Vec::new();
+
+    cbindgen::Builder::new()
+        .with_crate(manifest_dir())
+        .with_config(config)
+        .generate()
+        .expect("header generation failed")
+        .write(&mut header);
+
+    String::from_utf8(header).unwrap()
+}
+
+/// Build the `staticlib` and return its path.
+///
+/// Test builds link the library as `rlib` only, so it is built explicitly.
+fn staticlib() -> PathBuf {
+    let mut command = Command::new(env!("CARGO"));
+    command.args(["build", "--lib", "--package", env!("CARGO_PKG_NAME")]);
+
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+
+    assert!(
+        command.status().unwrap().success(),
+        "staticlib build failed"
+    );
+
+    // test executable is located at `target/<profile>/deps`
+    let exe = env::current_exe().unwrap();
+    exe.parent()
+        .and_then(Path::parent)
+        .unwrap()
+        .join("libpico_detect_c.a")
+}
+
+#[test]
+fn test_header_is_up_to_date() {
+    let path = manifest_dir().join("include").join("pico_detect.h");
+
+    if env::var_os("PICO_DETECT_UPDATE_HEADER").is_some() {
+        fs::write(&path, generate_header()).unwrap();
+    }
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        generate_header(),
+        "run with `PICO_DETECT_UPDATE_HEADER=1` to regenerate the header"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_c_program() {
+    let output = env::temp_dir().join(format!("pico_detect_c_test_{}", std::process::id()));
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_owned());
+
+    let status = Command::new(compiler)
+        .args(["-std=c99", "-Wall", "-Wextra", "-Werror"])
+        .arg(manifest_dir().join("tests").join("test.c"))
+        .arg("-I")
+        .arg(manifest_dir().join("include"))
+        .arg(staticlib())
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&output)
+        .status()
+        .expect("failed to run C compiler");
+    assert!(status.success(), "C program compilation failed");
+
+    let status = Command::new(&output).status().unwrap();
+    fs::remove_file(&output).ok();
+    assert!(status.success(), "C program failed");
+}
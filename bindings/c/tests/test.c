@@ -0,0 +1,172 @@
+/* C API smoke test, built and run by `c_api.rs`. */
+
+#include <math.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "pico_detect.h"
+
+#define CHECK(condition)                                                     \
+    do {                                                                     \
+        if (!(condition)) {                                                  \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, \
+                    #condition);                                             \
+            return 1;                                                        \
+        }                                                                    \
+    } while (0)
+
+typedef struct {
+    uint8_t data[256];
+    size_t len;
+} Buffer;
+
+static void push(Buffer *buffer, const void *data, size_t len) {
+    memcpy(buffer->data + buffer->len, data, len);
+    buffer->len += len;
+}
+
+/* models are stored in little-endian, except shaper which is big-endian */
+static void push_le(Buffer *buffer, uint32_t value) {
+    uint8_t bytes[4] = {value, value >> 8, value >> 16, value >> 24};
+    push(buffer, bytes, 4);
+}
+
+static void push_be(Buffer *buffer, uint32_t value) {
+    uint8_t bytes[4] = {value >> 24, value >> 16, value >> 8, value};
+    push(buffer, bytes, 4);
+}
+
+static uint32_t bits(float value) {
+    uint32_t result;
+    memcpy(&result, &value, 4);
+    return result;
+}
+
+static int test_detector(const PicoImage *image) {
+    Buffer model = {{0}, 0};
+    push_le(&model, 0);
+    push_le(&model, 0);
+    push_le(&model, 1); /* depth */
+    push_le(&model, 1); /* trees */
+    push_le(&model, 0); /* node */
+    push_le(&model, bits(1.0f));
+    push_le(&model, bits(1.0f));
+    push_le(&model, bits(-1.0f));
+
+    CHECK(pico_detector_load(model.data, model.len - 1) == NULL);
+
+    PicoDetector *detector = pico_detector_load(model.data, model.len);
+    CHECK(detector != NULL);
+
+    float score = 0.0f;
+    CHECK(pico_detector_classify(detector, image, 0, 0, 16, &score) == 1);
+    CHECK(score == 2.0f);
+
+    PicoDetectOptions options = pico_detect_options_default();
+    options.min_size = 32;
+    options.shift_factor = 0.5f;
+
+    PicoDetection detections[4];
+    ptrdiff_t count = pico_detector_detect(detector, image, &options, detections, 4);
+    CHECK(count > 0);
+
+    for (ptrdiff_t i = 0; i < count && i < 4; i++) {
+        CHECK(detections[i].score >= 2.0f);
+        CHECK(detections[i].size >= 32.0f);
+    }
+
+    CHECK(pico_detector_detect(detector, image, &options, NULL, 0) == count);
+
+    options.min_size = 0;
+    CHECK(pico_detector_detect(detector, image, &options, detections, 4) < 0);
+
+    /* factors keeping window sizes or positions unchanged are rejected */
+    const float scale_factors[] = {1.0f, 0.5f, NAN, INFINITY};
+    for (size_t i = 0; i < sizeof(scale_factors) / sizeof(scale_factors[0]); i++) {
+        options = pico_detect_options_default();
+        options.scale_factor = scale_factors[i];
+        CHECK(pico_detector_detect(detector, image, &options, detections, 4) < 0);
+    }
+
+    const float shift_factors[] = {0.0f, -0.5f, NAN};
+    for (size_t i = 0; i < sizeof(shift_factors) / sizeof(shift_factors[0]); i++) {
+        options = pico_detect_options_default();
+        options.shift_factor = shift_factors[i];
+        CHECK(pico_detector_detect(detector, image, &options, detections, 4) < 0);
+    }
+
+    pico_detector_free(detector);
+    return 0;
+}
+
+static int test_localizer(const PicoImage *image) {
+    Buffer model = {{0}, 0};
+    push_le(&model, 1); /* stages */
+    push_le(&model, bits(0.8f));
+    push_le(&model, 1); /* trees */
+    push_le(&model, 1); /* depth */
+    for (int i = 0; i < 1 + 2 * 2; i++) {
+        push_le(&model, 0);
+    }
+
+    PicoLocalizer *localizer = pico_localizer_load(model.data, model.len);
+    CHECK(localizer != NULL);
+
+    PicoPoint point;
+    CHECK(pico_localizer_localize(localizer, image, 20.0f, 30.0f, 10.0f, &point) == 0);
+    CHECK(point.x == 20.0f && point.y == 30.0f);
+    CHECK(pico_localizer_localize(localizer, image, 20.0f, 30.0f, 10.0f, NULL) < 0);
+
+    pico_localizer_free(localizer);
+    return 0;
+}
+
+static int test_shaper(const PicoImage *image) {
+    const float shape[4] = {0.25f, 0.25f, 0.75f, 0.75f};
+
+    Buffer model = {{0}, 0};
+    uint8_t version = 1;
+    push(&model, &version, 1);
+    push_be(&model, 2); /* rows */
+    push_be(&model, 2); /* points */
+    push_be(&model, 0); /* forests */
+    push_be(&model, 0); /* forest size */
+    push_be(&model, 1); /* depth */
+    push_be(&model, 0); /* features */
+    for (int i = 0; i < 4; i++) {
+        push_be(&model, bits(shape[i]));
+    }
+
+    PicoShaper *shaper = pico_shaper_load(model.data, model.len);
+    CHECK(shaper != NULL);
+    CHECK(pico_shaper_size(shaper) == 2);
+
+    PicoPoint points[2];
+    CHECK(pico_shaper_shape(shaper, image, 32.0f, 32.0f, 32.0f, points, 2) == 2);
+    CHECK(points[0].x < points[1].x);
+
+    pico_shaper_free(shaper);
+    return 0;
+}
+
+int main(void) {
+    enum { WIDTH = 64, HEIGHT = 64, STRIDE = 80 };
+    static uint8_t pixels[STRIDE * HEIGHT];
+    memset(pixels, 128, sizeof(pixels));
+
+    PicoImage image = {pixels, WIDTH, HEIGHT, STRIDE};
+
+    PicoImage invalid = image;
+    invalid.stride = WIDTH - 1;
+    CHECK(pico_detector_classify(NULL, &image, 0, 0, 16, NULL) < 0);
+
+    PicoPoint point;
+    CHECK(pico_localizer_localize(NULL, &invalid, 0.0f, 0.0f, 1.0f, &point) < 0);
+
+    if (test_detector(&image) || test_localizer(&image) || test_shaper(&image)) {
+        return 1;
+    }
+
+    printf("ok\n");
+    return 0;
+}
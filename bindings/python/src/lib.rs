@@ -0,0 +1,238 @@
+//! Python bindings of [`pico_detect`].
+//!
+//! Images are 2-D `numpy.uint8` arrays, which are borrowed without copying
+//! (any strides are supported). Results are returned as `numpy.float32` arrays.
+
+use std::path::PathBuf;
+
+use numpy::ndarray::{Array1, Array2, ArrayView2};
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyReadonlyArray2};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use pico_detect::clusterize::Clusterizer;
+use pico_detect::multiscale::Multiscaler;
+use pico_detect::{DetectMultiscale, GrayImageView, Rectangle, Square, Target};
+
+struct ImageView<'a>(ArrayView2<'a, u8>);
+
+impl GrayImageView for ImageView<'_> {
+    type Luma = u8;
+
+    #[inline]
+    fn size(&self) -> (u32, u32) {
+        let (rows, cols) = self.0.dim();
+        (cols as u32, rows as u32)
+    }
+
+    #[inline]
+    fn luma(&self, x: u32, y: u32) -> u8 {
+        self.0[[y as usize, x as usize]]
+    }
+}
+
+/// Read model bytes from `bytes`-like object or a file path.
+fn model_bytes(source: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
+    match source.extract::<Vec<u8>>() {
+        Ok(data) => Ok(data),
+        Err(_) => Ok(std::fs::read(source.extract::<PathBuf>()?)?),
+    }
+}
+
+fn value_error(error: impl ToString) -> PyErr {
+    PyValueError::new_err(error.to_string())
+}
+
+/// Object detector, see `pico_detect::Detector`.
+#[pyclass(frozen, module = "pico_detect")]
+struct Detector(pico_detect::Detector);
+
+#[pymethods]
+impl Detector {
+    /// Load detector from model `bytes` or a file path.
+    #[staticmethod]
+    fn load(source: &Bound<'_, PyAny>) -> PyResult<Self> {
+        pico_detect::Detector::load(model_bytes(source)?.as_slice())
+            .map(Self)
+            .map_err(value_error)
+    }
+
+    /// Classify a square region with the top-left corner at `(left, top)`,
+    /// returns score or `None` if the region is not an object.
+    fn classify(
+        &self,
+        image: PyReadonlyArray2<'_, u8>,
+        left: i32,
+        top: i32,
+        size: u32,
+    ) -> Option<f32> {
+        self.0
+            .classify(&ImageView(image.as_array()), Square::new(left, top, size))
+    }
+
+    /// Run multiscale detection with clustering.
+    ///
+    /// Returns `(N, 4)` array of `[x, y, size, score]` rows, where `x` and `y`
+    /// are region center coordinates.
+    #[pyo3(signature = (
+        image,
+        min_size = 100,
+        max_size = None,
+        shift_factor = 0.05,
+        scale_factor = 1.1,
+        intersection_threshold = None,
+        score_threshold = None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn detect<'py>(
+        &self,
+        py: Python<'py>,
+        image: PyReadonlyArray2<'py, u8>,
+        min_size: u32,
+        max_size: Option<u32>,
+        shift_factor: f32,
+        scale_factor: f32,
+        intersection_threshold: Option<f32>,
+        score_threshold: Option<f32>,
+    ) -> PyResult<Bound<'py, PyArray2<f32>>> {
+        let image = ImageView(image.as_array());
+        let (width, height) = image.size();
+
+        let multiscaler = Multiscaler::new(
+            min_size,
+            max_size.unwrap_or(width.min(height)),
+            shift_factor,
+            scale_factor,
+        )
+        .map_err(value_error)?;
+
+        let mut clusterizer = Clusterizer::default();
+        if let Some(value) = intersection_threshold {
+            clusterizer = clusterizer.intersection_threshold(value);
+        }
+        if let Some(value) = score_threshold {
+            clusterizer = clusterizer.score_threshold(value);
+        }
+
+        let detect = DetectMultiscale::builder()
+            .multiscaler(multiscaler)
+            .clusterizer(clusterizer)
+            .build()
+            .map_err(value_error)?;
+
+        let detections = py.detach(|| detect.run(&self.0, &image));
+
+        let rows: Vec<f32> = detections
+            .iter()
+            .flat_map(|detection| {
+                let region = detection.region();
+                [region.x(), region.y(), region.size(), detection.score()]
+            })
+            .collect();
+
+        Ok(Array2::from_shape_vec((detections.len(), 4), rows)
+            .expect("shape matches")
+            .into_pyarray(py))
+    }
+}
+
+/// Object localizer, see `pico_detect::Localizer`.
+#[pyclass(frozen, module = "pico_detect")]
+struct Localizer(pico_detect::Localizer);
+
+#[pymethods]
+impl Localizer {
+    /// Load localizer from model `bytes` or a file path.
+    #[staticmethod]
+    fn load(source: &Bound<'_, PyAny>) -> PyResult<Self> {
+        pico_detect::Localizer::load(model_bytes(source)?.as_slice())
+            .map(Self)
+            .map_err(value_error)
+    }
+
+    /// Localize an object inside the region with center `(x, y)`,
+    /// returns `[x, y]` array.
+    fn localize<'py>(
+        &self,
+        py: Python<'py>,
+        image: PyReadonlyArray2<'py, u8>,
+        x: f32,
+        y: f32,
+        size: f32,
+    ) -> Bound<'py, PyArray1<f32>> {
+        let image = ImageView(image.as_array());
+        let point = py.detach(|| self.0.localize(&image, Target::new(x, y, size)));
+
+        Array1::from_vec(vec![point.x, point.y]).into_pyarray(py)
+    }
+}
+
+/// Landmarks shaper, see `pico_detect::Shaper`.
+#[pyclass(frozen, module = "pico_detect")]
+struct Shaper(pico_detect::Shaper);
+
+#[pymethods]
+impl Shaper {
+    /// Load shaper from model `bytes` or a file path.
+    #[staticmethod]
+    fn load(source: &Bound<'_, PyAny>) -> PyResult<Self> {
+        pico_detect::Shaper::load(model_bytes(source)?.as_slice())
+            .map(Self)
+            .map_err(value_error)
+    }
+
+    /// Number of landmarks.
+    #[getter]
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+
+    /// Estimate landmarks inside the region with center `(x, y)`,
+    /// returns `(N, 2)` array of `[x, y]` rows.
+    fn shape<'py>(
+        &self,
+        py: Python<'py>,
+        image: PyReadonlyArray2<'py, u8>,
+        x: f32,
+        y: f32,
+        size: f32,
+    ) -> Bound<'py, PyArray2<f32>> {
+        let image = ImageView(image.as_array());
+        let rect = Rectangle::from(Target::new(x, y, size));
+        let points = py.detach(|| self.0.shape(&image, rect));
+
+        let rows: Vec<f32> = points.iter().flat_map(|point| [point.x, point.y]).collect();
+
+        Array2::from_shape_vec((points.len(), 2), rows)
+            .expect("shape matches")
+            .into_pyarray(py)
+    }
+}
+
+#[pymodule]
+#[pyo3(name = "pico_detect")]
+fn pico_detect_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Detector>()?;
+    m.add_class::<Localizer>()?;
+    m.add_class::<Shaper>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use numpy::ndarray::{s, Array2};
+
+    use super::*;
+
+    #[test]
+    fn test_image_view_strides() {
+        let array = Array2::from_shape_fn((4, 6), |(y, x)| (10 * y + x) as u8);
+        let view = array.slice(s![..;2, 1..;2]);
+        let image = ImageView(view);
+
+        assert_eq!(image.size(), (3, 2));
+        assert_eq!(image.luma(0, 0), 1);
+        assert_eq!(image.luma(2, 1), 25);
+        assert_eq!(image.luma_clamped([5, -1].into()), 5);
+    }
+}
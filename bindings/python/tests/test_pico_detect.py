@@ -0,0 +1,90 @@
+import struct
+
+import numpy as np
+import pytest
+
+from pico_detect import Detector, Localizer, Shaper
+
+
+def detector_model():
+    return bytes(8) + struct.pack("<ii", 1, 1) + bytes(4) + struct.pack("<fff", 1.0, 1.0, -1.0)
+
+
+def localizer_model():
+    return struct.pack("<ifii", 1, 0.8, 1, 1) + bytes(4 + 2 * 2 * 4)
+
+
+def shaper_model():
+    header = b"\x01" + struct.pack(">6I", 2, 2, 0, 0, 1, 0)
+    return header + struct.pack(">4f", 0.25, 0.25, 0.75, 0.75)
+
+
+@pytest.fixture
+def image():
+    return np.full((64, 64), 128, dtype=np.uint8)
+
+
+def test_detector_load_invalid():
+    with pytest.raises(ValueError):
+        Detector.load(detector_model()[:-1])
+
+
+def test_detector_load_path(tmp_path):
+    path = tmp_path / "model.bin"
+    path.write_bytes(detector_model())
+    assert Detector.load(path).classify(np.zeros((16, 16), np.uint8), 0, 0, 16) == 2.0
+
+
+def test_detector_detect(image):
+    detections = Detector.load(detector_model()).detect(image, min_size=32, shift_factor=0.5)
+
+    assert detections.dtype == np.float32
+    assert detections.ndim == 2 and detections.shape[1] == 4
+    assert len(detections) > 0
+    assert (detections[:, 2] >= 32).all()
+    assert (detections[:, 3] >= 2.0).all()
+
+
+def test_detector_detect_strided(image):
+    detector = Detector.load(detector_model())
+    padded = np.zeros((64, 80), np.uint8)
+    padded[:, :64] = image
+
+    np.testing.assert_array_equal(
+        detector.detect(padded[:, :64], min_size=32, shift_factor=0.5),
+        detector.detect(image, min_size=32, shift_factor=0.5),
+    )
+
+
+def test_detector_detect_invalid_options(image):
+    with pytest.raises(ValueError):
+        Detector.load(detector_model()).detect(image, min_size=0)
+
+
+@pytest.mark.parametrize(
+    "options",
+    [
+        {"scale_factor": 1.0},
+        {"scale_factor": float("nan")},
+        {"scale_factor": float("inf")},
+        {"shift_factor": 0.0},
+        {"shift_factor": float("nan")},
+    ],
+)
+def test_detector_detect_invalid_factors(image, options):
+    with pytest.raises(ValueError):
+        Detector.load(detector_model()).detect(image, **options)
+
+
+def test_localizer_localize(image):
+    point = Localizer.load(localizer_model()).localize(image, 20.0, 30.0, 10.0)
+    np.testing.assert_array_equal(point, np.array([20.0, 30.0], np.float32))
+
+
+def test_shaper_shape(image):
+    shaper = Shaper.load(shaper_model())
+    points = shaper.shape(image, 32.0, 32.0, 32.0)
+
+    assert shaper.size == 2
+    assert points.shape == (2, 2)
+    assert points[0, 0] < points[1, 0]
@@ -0,0 +1,284 @@
+//! WebAssembly bindings of [`pico_detect`].
+//!
+//! Images are passed as RGBA pixels (e.g. `ImageData.data`) and viewed
+//! as luma without copying, results are returned as plain JS objects.
+
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use pico_detect::clusterize::Clusterizer;
+use pico_detect::multiscale::Multiscaler;
+use pico_detect::nalgebra::Point2;
+use pico_detect::{DetectMultiscale, PackedFormat, PackedView, Rectangle, Target};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TYPES: &str = r#"
+export interface DetectOptions {
+    minSize?: number;
+    maxSize?: number;
+    shiftFactor?: number;
+    scaleFactor?: number;
+    intersectionThreshold?: number;
+    scoreThreshold?: number;
+}
+
+export interface Region {
+    x: number;
+    y: number;
+    size: number;
+}
+
+export interface Detection extends Region {
+    score: number;
+}
+
+export interface Point {
+    x: number;
+    y: number;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "DetectOptions")]
+    pub type DetectOptions;
+
+    #[wasm_bindgen(typescript_type = "Region")]
+    pub type Region;
+
+    #[wasm_bindgen(typescript_type = "Detection[]")]
+    pub type Detections;
+
+    #[wasm_bindgen(typescript_type = "Point")]
+    pub type Point;
+
+    #[wasm_bindgen(typescript_type = "Point[]")]
+    pub type Points;
+}
+
+/// Object detector, see [`pico_detect::Detector`].
+#[wasm_bindgen]
+pub struct Detector(pico_detect::Detector);
+
+#[wasm_bindgen]
+impl Detector {
+    /// Load detector from the model bytes.
+    #[wasm_bindgen(constructor)]
+    pub fn new(model: &[u8]) -> Result<Detector, JsValue> {
+        pico_detect::Detector::load(model)
+            .map(Self)
+            .map_err(|error| JsError::new(&error.to_string()).into())
+    }
+
+    /// Run multiscale detection with clustering on RGBA pixels.
+    pub fn detect(
+        &self,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        options: Option<DetectOptions>,
+    ) -> Result<Detections, JsValue> {
+        let image = gray_view(rgba, width, height)?;
+        let options: JsValue = options.map_or(JsValue::UNDEFINED, Into::into);
+
+        let min_size = number(&options, "minSize")?.map_or(100, |value| value as u32);
+        let max_size = number(&options, "maxSize")?.map_or(width.min(height), |value| value as u32);
+        let shift_factor = number(&options, "shiftFactor")?.map_or(0.05, |value| value as f32);
+        let scale_factor = number(&options, "scaleFactor")?.map_or(1.1, |value| value as f32);
+
+        let mut clusterizer = Clusterizer::default();
+        if let Some(value) = number(&options, "intersectionThreshold")? {
+            clusterizer = clusterizer.intersection_threshold(value as f32);
+        }
+        if let Some(value) = number(&options, "scoreThreshold")? {
+            clusterizer = clusterizer.score_threshold(value as f32);
+        }
+
+        let multiscaler = Multiscaler::new(min_size, max_size, shift_factor, scale_factor)
+            .map_err(|error| JsError::new(&error.to_string()))?;
+
+        let detect = DetectMultiscale::builder()
+            .multiscaler(multiscaler)
+            .clusterizer(clusterizer)
+            .build()
+            .map_err(|error| JsError::new(&error.to_string()))?;
+
+        let detections = Array::new();
+
+        for detection in detect.run(&self.0, &image) {
+            let object = region(detection.region())?;
+            set(&object, "score", detection.score())?;
+            detections.push(&object);
+        }
+
+        Ok(detections.unchecked_into())
+    }
+}
+
+/// Object localizer, see [`pico_detect::Localizer`].
+#[wasm_bindgen]
+pub struct Localizer(pico_detect::Localizer);
+
+#[wasm_bindgen]
+impl Localizer {
+    /// Load localizer from the model bytes.
+    #[wasm_bindgen(constructor)]
+    pub fn new(model: &[u8]) -> Result<Localizer, JsValue> {
+        pico_detect::Localizer::load(model)
+            .map(Self)
+            .map_err(|error| JsError::new(&error.to_string()).into())
+    }
+
+    /// Localize an object inside the region on RGBA pixels.
+    pub fn localize(
+        &self,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        region: Region,
+    ) -> Result<Point, JsValue> {
+        let image = gray_view(rgba, width, height)?;
+        let point = self.0.localize(&image, target(&region)?);
+
+        Ok(point_object(point)?.unchecked_into())
+    }
+}
+
+/// Landmarks shaper, see [`pico_detect::Shaper`].
+#[wasm_bindgen]
+pub struct Shaper(pico_detect::Shaper);
+
+#[wasm_bindgen]
+impl Shaper {
+    /// Load shaper from the model bytes.
+    #[wasm_bindgen(constructor)]
+    pub fn new(model: &[u8]) -> Result<Shaper, JsValue> {
+        pico_detect::Shaper::load(model)
+            .map(Self)
+            .map_err(|error| JsError::new(&error.to_string()).into())
+    }
+
+    /// Number of landmarks.
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.0.size()
+    }
+
+    /// Estimate landmarks inside the region on RGBA pixels.
+    pub fn shape(
+        &self,
+        rgba: &[u8],
+        width: u32,
+        height: u32,
+        region: Region,
+    ) -> Result<Points, JsValue> {
+        let image = gray_view(rgba, width, height)?;
+        let points = Array::new();
+
+        for point in self.0.shape(&image, Rectangle::from(target(&region)?)) {
+            let object = point_object(point)?;
+            points.push(&object);
+        }
+
+        Ok(points.unchecked_into())
+    }
+}
+
+/// View tightly packed RGBA pixels as luma with the same coefficients
+/// as `image` crate conversion to grayscale.
+fn rgba_view(rgba: &[u8], width: u32, height: u32) -> Option<PackedView<'_>> {
+    let size = (width as usize)
+        .checked_mul(height as usize)?
+        .checked_mul(4)?;
+
+    if size != rgba.len() {
+        return None;
+    }
+
+    PackedView::packed(rgba, width, height, PackedFormat::Rgba).ok()
+}
+
+fn gray_view(rgba: &[u8], width: u32, height: u32) -> Result<PackedView<'_>, JsError> {
+    rgba_view(rgba, width, height)
+        .ok_or_else(|| JsError::new("pixels length should be `width * height * 4`"))
+}
+
+fn number(object: &JsValue, key: &str) -> Result<Option<f64>, JsValue> {
+    if object.is_undefined() || object.is_null() {
+        return Ok(None);
+    }
+
+    let value = Reflect::get(object, &JsValue::from_str(key))?;
+
+    if value.is_undefined() || value.is_null() {
+        return Ok(None);
+    }
+
+    value
+        .as_f64()
+        .map(Some)
+        .ok_or_else(|| JsError::new(&format!("`{}` should be a number", key)).into())
+}
+
+fn target(region: &JsValue) -> Result<Target, JsValue> {
+    let field = |key| {
+        number(region, key)?
+            .ok_or_else(|| JsValue::from(JsError::new(&format!("`{}` is required", key))))
+    };
+
+    Ok(Target::new(
+        field("x")? as f32,
+        field("y")? as f32,
+        field("size")? as f32,
+    ))
+}
+
+fn set(object: &Object, key: &str, value: f32) -> Result<(), JsValue> {
+    Reflect::set(
+        object,
+        &JsValue::from_str(key),
+        &JsValue::from_f64(value as f64),
+    )
+    .map(|_| ())
+}
+
+fn region(target: &Target) -> Result<Object, JsValue> {
+    let object = Object::new();
+    set(&object, "x", target.x())?;
+    set(&object, "y", target.y())?;
+    set(&object, "size", target.size())?;
+    Ok(object)
+}
+
+fn point_object(point: Point2<f32>) -> Result<Object, JsValue> {
+    let object = Object::new();
+    set(&object, "x", point.x)?;
+    set(&object, "y", point.y)?;
+    Ok(object)
+}
+
+#[cfg(test)]
+mod tests {
+    use pico_detect::image::{DynamicImage, RgbaImage};
+    use pico_detect::GrayImageView;
+
+    use super::*;
+
+    #[test]
+    fn test_rgba_view() {
+        let rgba = [
+            255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 10, 10, 10, 0,
+        ];
+        let view = rgba_view(&rgba, 2, 2).unwrap();
+        let luma = [(0, 0), (1, 0), (0, 1), (1, 1)].map(|(x, y)| view.luma(x, y));
+
+        assert_eq!(luma, [54, 182, 18, 10]);
+        assert!(rgba_view(&rgba, 2, 1).is_none());
+        assert!(rgba_view(&rgba[..12], 2, 2).is_none());
+
+        // same as `image` crate conversion
+        let image = RgbaImage::from_raw(2, 2, rgba.to_vec()).unwrap();
+        assert_eq!(DynamicImage::ImageRgba8(image).to_luma8().as_raw(), &luma);
+    }
+}
@@ -0,0 +1,145 @@
+//! Run with `wasm-pack test --node bindings/wasm`.
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use pico_detect_wasm::{Detector, Localizer, Region, Shaper};
+
+fn detector_model() -> Vec<u8> {
+    let mut data = vec![0u8; 8];
+    data.extend(1i32.to_le_bytes());
+    data.extend(1i32.to_le_bytes());
+    data.extend([0u8; 4]);
+    data.extend([1.0f32, 1.0, -1.0].iter().flat_map(|v| v.to_le_bytes()));
+    data
+}
+
+fn localizer_model() -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend(1i32.to_le_bytes());
+    data.extend(0.8f32.to_le_bytes());
+    data.extend(1i32.to_le_bytes());
+    data.extend(1i32.to_le_bytes());
+    data.extend([0u8; 4 + 2 * 2 * 4]);
+    data
+}
+
+fn shaper_model() -> Vec<u8> {
+    let shape = [0.25f32, 0.25, 0.75, 0.75];
+    let mut data = vec![1u8];
+    data.extend(2u32.to_be_bytes());
+    data.extend(2u32.to_be_bytes());
+    data.extend([0u32, 0, 1, 0].iter().flat_map(|v| v.to_be_bytes()));
+    data.extend(shape.iter().flat_map(|v| v.to_be_bytes()));
+    data
+}
+
+fn rgba(width: u32, height: u32) -> Vec<u8> {
+    vec![128u8; (width * height * 4) as usize]
+}
+
+fn get(object: &JsValue, key: &str) -> f64 {
+    Reflect::get(object, &key.into()).unwrap().as_f64().unwrap()
+}
+
+fn region(x: f64, y: f64, size: f64) -> Region {
+    let object = Object::new();
+    Reflect::set(&object, &"x".into(), &x.into()).unwrap();
+    Reflect::set(&object, &"y".into(), &y.into()).unwrap();
+    Reflect::set(&object, &"size".into(), &size.into()).unwrap();
+    object.unchecked_into()
+}
+
+#[wasm_bindgen_test]
+fn test_detect() {
+    let detector = Detector::new(&detector_model()).unwrap();
+
+    let options = Object::new();
+    Reflect::set(&options, &"minSize".into(), &32.into()).unwrap();
+    Reflect::set(&options, &"shiftFactor".into(), &0.5.into()).unwrap();
+
+    let detections: Array = detector
+        .detect(&rgba(64, 64), 64, 64, Some(options.unchecked_into()))
+        .unwrap()
+        .unchecked_into();
+
+    // each window scores `2`, clustered scores are weighted by overlaps
+    let expected: [[f32; 4]; 14] = [
+        [17.5, 17.5, 35.0, 5.0901194],
+        [33.25, 16.75, 33.5, 3.578361],
+        [48.0, 16.0, 32.0, 2.0],
+        [16.75, 33.25, 33.5, 3.578361],
+        [33.25, 33.25, 33.5, 3.4922361],
+        [48.0, 32.0, 32.0, 2.0],
+        [16.0, 48.0, 32.0, 2.0],
+        [32.0, 48.0, 32.0, 2.0],
+        [48.0, 48.0, 32.0, 2.0],
+        [39.25, 19.75, 39.5, 3.6358347],
+        [19.75, 39.25, 39.5, 3.6358347],
+        [39.25, 39.25, 39.5, 3.5592256],
+        [22.5, 22.5, 45.0, 5.060497],
+        [29.0, 29.0, 58.0, 5.0855055],
+    ];
+
+    let actual: Vec<[f32; 4]> = detections
+        .iter()
+        .map(|detection| ["x", "y", "size", "score"].map(|key| get(&detection, key) as f32))
+        .collect();
+
+    assert_eq!(actual, expected);
+}
+
+#[wasm_bindgen_test]
+fn test_detect_invalid_factors() {
+    let detector = Detector::new(&detector_model()).unwrap();
+
+    for (key, value) in [
+        ("scaleFactor", 1.0),
+        ("scaleFactor", f64::NAN),
+        ("scaleFactor", f64::INFINITY),
+        ("shiftFactor", 0.0),
+        ("shiftFactor", f64::NAN),
+    ] {
+        let options = Object::new();
+        Reflect::set(&options, &key.into(), &value.into()).unwrap();
+
+        assert!(detector
+            .detect(&rgba(64, 64), 64, 64, Some(options.unchecked_into()))
+            .is_err());
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_detect_invalid_input() {
+    let detector = Detector::new(&detector_model()).unwrap();
+
+    assert!(detector.detect(&rgba(64, 63), 64, 64, None).is_err());
+    assert!(Detector::new(&detector_model()[..10]).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_localize() {
+    let localizer = Localizer::new(&localizer_model()).unwrap();
+    let point = localizer
+        .localize(&rgba(64, 64), 64, 64, region(20.0, 30.0, 10.0))
+        .unwrap();
+
+    assert_eq!(get(&point, "x"), 20.0);
+    assert_eq!(get(&point, "y"), 30.0);
+}
+
+#[wasm_bindgen_test]
+fn test_shape() {
+    let shaper = Shaper::new(&shaper_model()).unwrap();
+    assert_eq!(shaper.size(), 2);
+
+    let points: Array = shaper
+        .shape(&rgba(64, 64), 64, 64, region(32.0, 32.0, 32.0))
+        .unwrap()
+        .unchecked_into();
+
+    assert_eq!(points.length(), 2);
+    assert!(get(&points.get(0), "x") < get(&points.get(1), "x"));
+}
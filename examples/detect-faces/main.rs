@@ -4,9 +4,6 @@ extern crate pico_detect;
 mod models;
 
 mod args;
-
-mod face;
-mod shape;
 mod utils;
 
 use rand::SeedableRng;
@@ -15,8 +12,7 @@ use rand_xoshiro::Xoroshiro128PlusPlus;
 use ab_glyph::FontRef;
 use anyhow::{Context, Result};
 
-use face::Face;
-use shape::Shape5;
+use pico_detect::{ColorView, FacePipeline};
 use utils::{draw_face, print_faces_data};
 
 fn main() -> Result<()> {
@@ -28,41 +24,28 @@ fn main() -> Result<()> {
     let image = image::open(&args.input).context("Failed to load image file.")?;
 
     let (detector, localizer, shaper) = args.load_models()?;
-    let (multiscale, localize) = args.init(&image)?;
-
-    let mut rng = Xoroshiro128PlusPlus::seed_from_u64(42);
-
-    let gray = image.to_owned().into_luma8();
+    let (detect, localize) = args.init(&image)?;
 
-    let faces: Vec<Face> = multiscale
-        .run(&detector, &gray)
-        .iter()
-        .map(|d| {
-            let roi = *d.region();
+    let pipeline = FacePipeline::builder()
+        .detector(detector)
+        .shaper(shaper)
+        .localizer(localizer)
+        .detect(detect)
+        .localize(localize)
+        .build()?;
 
-            let shape = shaper.shape(&gray, roi.into());
+    let mut rng = Xoroshiro128PlusPlus::seed_from_u64(42);
 
-            let (left_eye_roi, right_eye_roi) = Shape5::find_eyes_roi(&shape);
-            let left_pupil = localize.run(&localizer, &mut rng, &gray, left_eye_roi.into());
-            let right_pupil = localize.run(&localizer, &mut rng, &gray, right_eye_roi.into());
+    let mut rgb = image.into_rgb8();
 
-            Face {
-                region: roi.into(),
-                shape,
-                score: d.score(),
-                pupils: (left_pupil, right_pupil),
-            }
-        })
-        .collect();
+    let faces = pipeline.run(&mut rng, &ColorView::new(&rgb));
 
     if args.verbose {
         print_faces_data(&faces);
     }
 
-    let mut rgb = image.into_rgb8();
-
     for face in faces.iter() {
-        draw_face(&mut rgb, &face, &font, 12.0);
+        draw_face(&mut rgb, face, &font, 12.0);
     }
 
     rgb.save(args.output).context("Cannot write output image")?;
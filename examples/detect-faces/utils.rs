@@ -2,13 +2,13 @@ use ab_glyph::FontRef;
 use image::{Rgb, RgbImage};
 use imageproc::drawing;
 
-use crate::face::Face;
+use pico_detect::Face;
 
 pub fn draw_face(image: &mut RgbImage, face: &Face, font: &FontRef<'_>, scale: f32) {
     drawing::draw_hollow_rect_mut(image, face.region.into(), Rgb([0, 0, 255]));
 
     let color = Rgb([0, 255, 0]);
-    for (i, point) in face.shape.iter().enumerate() {
+    for (i, point) in face.landmarks.points().iter().enumerate() {
         let x = point.x as i32;
         let y = point.y as i32;
         drawing::draw_cross_mut(image, color, x, y);
@@ -28,7 +28,7 @@ pub fn print_faces_data(faces: &[Face]) {
         print!("\t");
         println!("score: {}", face.score);
 
-        for (i, point) in face.shape.iter().enumerate() {
+        for (i, point) in face.landmarks.points().iter().enumerate() {
             print!("\t");
             println!("point {}: {}", i, &point);
         }
@@ -37,6 +37,6 @@ pub fn print_faces_data(faces: &[Face]) {
         println!("left  pupil: {}", &face.pupils.0);
         print!("\t");
         println!("right pupil: {}", &face.pupils.1);
-        print!("\n");
+        println!();
     }
 }
@@ -64,7 +64,7 @@ fn main() -> Result<()> {
         }
         ModelType::Shaper => {
             let shaper = Shaper::load(file)?;
-            let shape = shaper.shape(&image, square.into());
+            let shape = shaper.shape(&image, square);
             println!("i,x,y");
             for (i, point) in shape.iter().enumerate() {
                 println!("{},{},{}", i, point.x as i32, point.y as i32);
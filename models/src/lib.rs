@@ -0,0 +1,15 @@
+//! Default models of [`pico-detect`](https://crates.io/crates/pico-detect),
+//! use them with its `bundled-models` feature.
+//!
+//! Models are kept in a separate crate, so they are downloaded
+//! only by dependents embedding them.
+#![no_std]
+
+/// Raw human face detector model (`face.detector.bin`).
+pub static FACE_DETECTOR: &[u8] = include_bytes!("../face.detector.bin");
+
+/// Raw human eye pupil localizer model (`pupil.localizer.bin`).
+pub static PUPIL_LOCALIZER: &[u8] = include_bytes!("../pupil.localizer.bin");
+
+/// Raw human 5 face landmarks shaper model (`face-5.shaper.bin`).
+pub static FACE5_SHAPER: &[u8] = include_bytes!("../face-5.shaper.bin");
@@ -0,0 +1,86 @@
+use nalgebra::Point2;
+
+/// Handling of pixels sampled outside of the image.
+///
+/// Used by [`Detector`](crate::Detector), [`Localizer`](crate::Localizer)
+/// and `Shaper` for windows crossing the image border.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BorderMode {
+    /// Take the value of the nearest edge pixel.
+    #[default]
+    Clamp,
+    /// Substitute the constant value in `[0, 255]` range, which is scaled
+    /// to the nominal range of the image luminance type.
+    Constant(u8),
+    /// Mirror coordinates over the image edge, the edge pixel is repeated.
+    Reflect,
+    /// Wrap coordinates around to the opposite edge.
+    Wrap,
+    /// Reject the whole window if any sample is outside of the image.
+    Reject,
+}
+
+impl BorderMode {
+    /// Substitute zero value.
+    pub const ZERO: Self = Self::Constant(0);
+
+    /// Map the point to the pixel inside the image with `(width, height)` dimensions.
+    ///
+    /// Returns `None` for points outside of the image with [`Constant`](Self::Constant)
+    /// and [`Reject`](Self::Reject) modes.
+    #[inline]
+    pub fn map(self, point: Point2<i32>, dimensions: (u32, u32)) -> Option<Point2<u32>> {
+        let (width, height) = dimensions;
+        let (x, y) = (point.x as u32, point.y as u32);
+
+        if point.x >= 0 && point.y >= 0 && x < width && y < height {
+            return Some(Point2::new(x, y));
+        }
+
+        let map = |value: i32, length: u32| -> Option<u32> {
+            let length = length as i32;
+
+            match self {
+                Self::Clamp => Some(value.clamp(0, length - 1) as u32),
+                Self::Reflect => {
+                    let value = value.rem_euclid(2 * length);
+                    Some(value.min(2 * length - 1 - value) as u32)
+                }
+                Self::Wrap => Some(value.rem_euclid(length) as u32),
+                Self::Constant(_) | Self::Reject => None,
+            }
+        };
+
+        Some(Point2::new(map(point.x, width)?, map(point.y, height)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_border_mode_map() {
+        let dimensions = (4, 3);
+        let map = |mode: BorderMode, x, y| mode.map(Point2::new(x, y), dimensions);
+
+        for mode in [
+            BorderMode::Clamp,
+            BorderMode::ZERO,
+            BorderMode::Reflect,
+            BorderMode::Wrap,
+            BorderMode::Reject,
+        ] {
+            assert_eq!(map(mode, 3, 2), Some(Point2::new(3, 2)));
+        }
+
+        assert_eq!(map(BorderMode::Clamp, -2, 5), Some(Point2::new(0, 2)));
+        assert_eq!(map(BorderMode::Reflect, -1, 3), Some(Point2::new(0, 2)));
+        assert_eq!(map(BorderMode::Reflect, -3, 4), Some(Point2::new(2, 1)));
+        assert_eq!(map(BorderMode::Reflect, 9, -7), Some(Point2::new(1, 0)));
+        assert_eq!(map(BorderMode::Wrap, -1, 3), Some(Point2::new(3, 0)));
+        assert_eq!(map(BorderMode::Wrap, 9, -4), Some(Point2::new(1, 2)));
+        assert_eq!(map(BorderMode::Constant(42), -1, 0), None);
+        assert_eq!(map(BorderMode::Reject, 0, 3), None);
+    }
+}
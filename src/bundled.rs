@@ -0,0 +1,36 @@
+//! Default models embedded with `bundled-models` feature.
+//!
+//! Models are shipped by `pico-detect-models` crate and parsed once on the first use.
+
+use std::sync::OnceLock;
+
+pub use pico_detect_models::{FACE5_SHAPER, FACE_DETECTOR, PUPIL_LOCALIZER};
+
+use crate::{Detector, Localizer, Shaper};
+
+impl Detector {
+    /// Returns bundled human face detector.
+    #[inline]
+    pub fn face() -> &'static Self {
+        static MODEL: OnceLock<Detector> = OnceLock::new();
+        MODEL.get_or_init(|| Self::load(FACE_DETECTOR).expect("invalid bundled face detector"))
+    }
+}
+
+impl Localizer {
+    /// Returns bundled human eye pupil localizer.
+    #[inline]
+    pub fn pupil() -> &'static Self {
+        static MODEL: OnceLock<Localizer> = OnceLock::new();
+        MODEL.get_or_init(|| Self::load(PUPIL_LOCALIZER).expect("invalid bundled pupil localizer"))
+    }
+}
+
+impl Shaper {
+    /// Returns bundled human 5 face landmarks shaper.
+    #[inline]
+    pub fn face5() -> &'static Self {
+        static MODEL: OnceLock<Shaper> = OnceLock::new();
+        MODEL.get_or_init(|| Self::load(FACE5_SHAPER).expect("invalid bundled face shaper"))
+    }
+}
@@ -0,0 +1,213 @@
+mod template;
+
+use image::{ImageBuffer, Pixel, Primitive};
+use imageproc::{
+    definitions::Clamp,
+    geometric_transformations::{warp_into_with, Interpolation},
+};
+use nalgebra::{Point2, SimilarityMatrix2};
+
+pub use template::ChipTemplate;
+
+/// Aligned object chip extraction parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChipExtractor {
+    /// Canonical landmark positions in the chip.
+    pub template: ChipTemplate,
+    /// Chip width and height in pixels.
+    pub size: u32,
+    /// Padding around the template relative to the chip size.
+    pub padding: f32,
+}
+
+impl ChipExtractor {
+    /// Creates a new extractor of chips of the specified size without padding.
+    #[inline]
+    pub fn new(template: ChipTemplate, size: u32) -> Self {
+        Self {
+            template,
+            size,
+            padding: 0.0,
+        }
+    }
+
+    /// Set the padding around the template relative to the chip size.
+    #[inline]
+    pub fn padding(self, value: f32) -> Self {
+        Self {
+            padding: value,
+            ..self
+        }
+    }
+
+    /// Find similarity transform from the chip to the image coordinates.
+    /// See [`chip_transform`] for more details.
+    #[inline]
+    pub fn transform(&self, shape: &[Point2<f32>]) -> Option<SimilarityMatrix2<f32>> {
+        chip_transform(shape, &self.template, self.size, self.padding)
+    }
+
+    /// Extract aligned chip from the image. See [`extract_chip`] for more details.
+    #[inline]
+    pub fn extract<P>(
+        &self,
+        image: &ImageBuffer<P, Vec<P::Subpixel>>,
+        shape: &[Point2<f32>],
+    ) -> Option<ImageBuffer<P, Vec<P::Subpixel>>>
+    where
+        P: Pixel + Send + Sync,
+        P::Subpixel: Send + Sync + Into<f32> + Clamp<f32>,
+    {
+        extract_chip(image, shape, &self.template, self.size, self.padding)
+    }
+}
+
+/// Find similarity transform from the chip to the image coordinates
+/// which aligns template with the shape in the least squares sense.
+///
+/// ### Arguments
+///
+/// * `shape` -- landmarks found by [`crate::Shaper`];
+/// * `template` -- canonical landmark positions;
+/// * `size` -- chip size;
+/// * `padding` -- padding around the template relative to the chip size.
+///
+/// ### Returns
+///
+/// * `None` -- if the shape does not have landmarks required by the template
+///   or the transform cannot be estimated.
+#[inline]
+pub fn chip_transform(
+    shape: &[Point2<f32>],
+    template: &ChipTemplate,
+    size: u32,
+    padding: f32,
+) -> Option<SimilarityMatrix2<f32>> {
+    let scale = size as f32 / 2.0f32.mul_add(padding, 1.0);
+
+    let (from, to): (Vec<Point2<f32>>, Vec<Point2<f32>>) = template
+        .points()
+        .iter()
+        .map(|(index, point)| {
+            shape.get(*index).map(|landmark| {
+                (
+                    Point2::from(point.coords.add_scalar(padding) * scale),
+                    *landmark,
+                )
+            })
+        })
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+
+    similarity_least_squares::from_point_slices(&from, &to, f32::EPSILON, 0)
+}
+
+/// Extract aligned chip from the image using bilinear interpolation.
+///
+/// Chip pixels outside of the image are black.
+/// See [`chip_transform`] for the arguments description.
+#[inline]
+pub fn extract_chip<P>(
+    image: &ImageBuffer<P, Vec<P::Subpixel>>,
+    shape: &[Point2<f32>],
+    template: &ChipTemplate,
+    size: u32,
+    padding: f32,
+) -> Option<ImageBuffer<P, Vec<P::Subpixel>>>
+where
+    P: Pixel + Send + Sync,
+    P::Subpixel: Send + Sync + Into<f32> + Clamp<f32>,
+{
+    let transform = chip_transform(shape, template, size, padding)?;
+
+    let mut chip = ImageBuffer::new(size, size);
+
+    warp_into_with(
+        image,
+        |x, y| {
+            let point = transform * Point2::new(x, y);
+            (point.x, point.y)
+        },
+        Interpolation::Bilinear,
+        *P::from_slice(&[Primitive::DEFAULT_MIN_VALUE; 4][..P::CHANNEL_COUNT as usize]),
+        &mut chip,
+    );
+
+    Some(chip)
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{GrayImage, Luma};
+    use nalgebra::{Rotation2, Translation2};
+
+    use super::*;
+
+    fn shape(
+        template: &ChipTemplate,
+        transform: &SimilarityMatrix2<f32>,
+        scale: f32,
+    ) -> Vec<Point2<f32>> {
+        template
+            .points()
+            .iter()
+            .map(|(_, point)| transform * (point * scale))
+            .collect()
+    }
+
+    #[test]
+    fn test_chip_transform() {
+        let template = ChipTemplate::face5();
+        let test =
+            SimilarityMatrix2::from_parts(Translation2::new(40.0, 30.0), Rotation2::new(0.3), 1.5);
+
+        let extractor = ChipExtractor::new(template.clone(), 100);
+        let transform = extractor
+            .transform(&shape(&template, &test, 100.0))
+            .unwrap();
+
+        assert_abs_diff_eq!(
+            transform.to_homogeneous(),
+            test.to_homogeneous(),
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn test_chip_transform_small_shape() {
+        let extractor = ChipExtractor::new(ChipTemplate::face68(), 100);
+        assert!(extractor.transform(&[Point2::origin(); 5]).is_none());
+    }
+
+    #[test]
+    fn test_extract_chip() {
+        let template = ChipTemplate::face5();
+        let test = SimilarityMatrix2::from_parts(
+            Translation2::new(60.0, 20.0),
+            Rotation2::identity(),
+            0.5,
+        );
+
+        let image = GrayImage::from_fn(200, 200, |x, _| Luma([x as u8]));
+
+        let padding = 0.25;
+        let size = 150;
+        let scale = size as f32 / (1.0 + 2.0 * padding);
+
+        let landmarks = shape(&template, &test, scale);
+        let chip = ChipExtractor::new(template.clone(), size)
+            .padding(padding)
+            .extract(&image, &landmarks)
+            .unwrap();
+
+        assert_eq!(chip.dimensions(), (size, size));
+
+        for ((_, point), landmark) in template.points().iter().zip(landmarks.iter()) {
+            let chip_point = (point.coords.add_scalar(padding) * scale).map(|v| v.round() as u32);
+            let Luma([value]) = *chip.get_pixel(chip_point.x, chip_point.y);
+
+            assert_abs_diff_eq!(value as f32, landmark.x, epsilon = 1.0);
+        }
+    }
+}
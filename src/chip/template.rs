@@ -0,0 +1,75 @@
+use nalgebra::Point2;
+
+use crate::landmarks::{face5, face68};
+
+/// Canonical landmark positions in an aligned face chip.
+///
+/// Positions are normalized to `[0, 1]` range of the chip without padding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChipTemplate {
+    points: Vec<(usize, Point2<f32>)>,
+}
+
+impl ChipTemplate {
+    /// Creates a template from pairs of a landmark index and its normalized position.
+    #[inline]
+    pub fn new(points: Vec<(usize, Point2<f32>)>) -> Self {
+        Self { points }
+    }
+
+    /// Template for the 5 face landmarks model (`face-5.shaper.bin`),
+    /// the same as used by dlib `get_face_chip_details`.
+    #[inline]
+    pub fn face5() -> Self {
+        Self::new(vec![
+            (
+                face5::LEFT_EYE_OUTER,
+                Point2::new(0.859_567_5, 0.213_498_15),
+            ),
+            (
+                face5::LEFT_EYE_INNER,
+                Point2::new(0.646_060_5, 0.228_967_44),
+            ),
+            (
+                face5::RIGHT_EYE_OUTER,
+                Point2::new(0.120_575_06, 0.213_727_45),
+            ),
+            (
+                face5::RIGHT_EYE_INNER,
+                Point2::new(0.334_085_06, 0.229_064_24),
+            ),
+            (face5::NOSE_BASE, Point2::new(0.490_112_3, 0.627_797_5)),
+        ])
+    }
+
+    /// Template for the 68 face landmarks model in the iBUG 300-W layout
+    /// using eye corners and nose base to match [`ChipTemplate::face5`] chips.
+    #[inline]
+    pub fn face68() -> Self {
+        Self::new(vec![
+            (
+                face68::LEFT_EYE_OUTER,
+                Point2::new(0.859_567_5, 0.213_498_15),
+            ),
+            (
+                face68::LEFT_EYE_INNER,
+                Point2::new(0.646_060_5, 0.228_967_44),
+            ),
+            (
+                face68::RIGHT_EYE_OUTER,
+                Point2::new(0.120_575_06, 0.213_727_45),
+            ),
+            (
+                face68::RIGHT_EYE_INNER,
+                Point2::new(0.334_085_06, 0.229_064_24),
+            ),
+            (face68::NOSE_BASE, Point2::new(0.490_112_3, 0.627_797_5)),
+        ])
+    }
+
+    /// Returns pairs of a landmark index and its normalized position.
+    #[inline]
+    pub fn points(&self) -> &[(usize, Point2<f32>)] {
+        &self.points
+    }
+}
@@ -0,0 +1,98 @@
+//! Rust source code generation of models for build scripts.
+//!
+//! Generated code contains a `static` model without heap allocations,
+//! for example in `build.rs`:
+//!
+//! ```no_run
+//! use std::{env, fs, path::Path};
+//!
+//! let model = fs::read("models/face.detector.bin").unwrap();
+//! let source = pico_detect::codegen::detector("FACE_DETECTOR", model.as_slice()).unwrap();
+//!
+//! let path = Path::new(&env::var("OUT_DIR").unwrap()).join("face_detector.rs");
+//! fs::write(path, source).unwrap();
+//! ```
+//!
+//! and then in the crate sources:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/face_detector.rs"));
+//!
+//! let score = FACE_DETECTOR.classify(&image, square);
+//! ```
+
+use std::fmt::Write;
+use std::io::{Error, Read};
+
+use crate::Detector;
+
+/// Number of array items per line of generated code.
+const ITEMS_PER_LINE: usize = 6;
+
+/// Generate a `pub static` [`StaticDetector`](crate::StaticDetector)
+/// definition from a pico detector model.
+///
+/// ### Arguments
+///
+/// * `name` -- name of the generated static item;
+/// * `readable` -- pico detector model source.
+pub fn detector(name: &str, readable: impl Read) -> Result<String, Error> {
+    let detector = Detector::load(readable)?;
+    let model = detector.view();
+
+    let mut source = String::new();
+    writeln!(
+        source,
+        "/// Detector model generated by `pico_detect::codegen`."
+    )
+    .unwrap();
+    writeln!(
+        source,
+        "pub static {}: ::pico_detect::StaticDetector<{}> = ::pico_detect::StaticDetector::new(",
+        name, model.depth
+    )
+    .unwrap();
+
+    for values in [model.nodes, model.predictions, model.thresholds] {
+        write_array(&mut source, values);
+    }
+
+    writeln!(source, ");").unwrap();
+
+    Ok(source)
+}
+
+fn write_array(source: &mut String, values: &[[u8; 4]]) {
+    writeln!(source, "    &[").unwrap();
+
+    for line in values.chunks(ITEMS_PER_LINE) {
+        let items: Vec<String> = line.iter().map(|value| format!("{:?}", value)).collect();
+        writeln!(source, "        {},", items.join(", ")).unwrap();
+    }
+
+    writeln!(source, "    ],").unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::detect::model_bytes;
+
+    use super::*;
+
+    #[test]
+    fn test_detector_source() {
+        let data = model_bytes(1, [(vec![[0, 129, 0, 127]], vec![1.0, -1.0], 0.5)]);
+
+        let source = detector("MODEL", data.as_slice()).unwrap();
+
+        assert_eq!(
+            source,
+            "/// Detector model generated by `pico_detect::codegen`.\n\
+             pub static MODEL: ::pico_detect::StaticDetector<1> = ::pico_detect::StaticDetector::new(\n    \
+                 &[\n        [0, 129, 0, 127],\n    ],\n    \
+                 &[\n        [0, 0, 128, 63], [0, 0, 128, 191],\n    ],\n    \
+                 &[\n        [0, 0, 0, 63],\n    ],\n\
+             );\n"
+        );
+    }
+}
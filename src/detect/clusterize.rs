@@ -1,9 +1,12 @@
-use crate::geometry::{intersection_over_union, Square, Target};
+use alloc::{vec, vec::Vec};
+use core::f32::consts::PI;
+
+use crate::geometry::{Rectangle, RotatedRectangle, Square, Target};
 use crate::traits::Region;
 
 use super::detection::Detection;
 
-use nalgebra::Point2;
+use nalgebra::{ComplexField, Point2};
 
 /// Clustering parameters for object detection results.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -41,6 +44,37 @@ impl Clusterizer {
             dest,
         );
     }
+
+    /// Run clustering on the provided detection data keeping width and height of regions.
+    #[inline]
+    pub fn clusterize_rect<R: Region + Copy>(
+        &self,
+        data: &mut [Detection<R>],
+        dest: &mut Vec<Detection<Rectangle>>,
+    ) {
+        clusterize_rect(
+            data,
+            self.intersection_threshold,
+            self.score_threshold,
+            dest,
+        );
+    }
+
+    /// Run clustering on the provided detection data with any output region,
+    /// see [`clusterize_into`].
+    #[inline]
+    pub fn clusterize_into<R, T>(&self, data: &mut [Detection<R>], dest: &mut Vec<Detection<T>>)
+    where
+        R: Region + Copy,
+        T: Region + FromCluster<R>,
+    {
+        clusterize_into(
+            data,
+            self.intersection_threshold,
+            self.score_threshold,
+            dest,
+        );
+    }
 }
 
 impl Default for Clusterizer {
@@ -55,6 +89,91 @@ impl Default for Clusterizer {
     }
 }
 
+/// Region built from a cluster of overlapping detected regions.
+pub trait FromCluster<R> {
+    /// Create a region from the non-empty cluster of regions.
+    fn from_cluster(regions: &[R]) -> Self;
+}
+
+/// Center point is the mean of top-left corners shifted by the half of the mean width.
+impl<R: Region> FromCluster<R> for Target {
+    #[inline]
+    fn from_cluster(regions: &[R]) -> Self {
+        let scale = (regions.len() as f32).recip();
+
+        let mut point = Point2::origin();
+        let mut size = 0;
+
+        for region in regions.iter() {
+            point += region.top_left().coords;
+            size += region.width();
+        }
+
+        let size = (size as f32) * scale;
+
+        let mut point: Point2<f32> = point.cast();
+
+        point.coords.scale_mut(scale);
+        point.coords.add_scalar_mut(size / 2.0);
+
+        Target { point, size }
+    }
+}
+
+/// Mean of top-left corners, widths and heights rounded to the nearest integer.
+impl<R: Region> FromCluster<R> for Rectangle {
+    #[inline]
+    fn from_cluster(regions: &[R]) -> Self {
+        let scale = (regions.len() as f32).recip();
+        let mean = |value: i64| ComplexField::round(value as f32 * scale);
+
+        let (mut left, mut top, mut width, mut height) = (0, 0, 0, 0);
+
+        for region in regions.iter() {
+            left += region.left() as i64;
+            top += region.top() as i64;
+            width += region.width() as i64;
+            height += region.height() as i64;
+        }
+
+        Rectangle::new(
+            mean(left) as i32,
+            mean(top) as i32,
+            mean(width) as u32,
+            mean(height) as u32,
+        )
+    }
+}
+
+/// Mean of center points, sizes and angles, the latter relative to the first
+/// region angle and wrapped to `[-π/2, π/2]` since rectangles are symmetric.
+impl FromCluster<RotatedRectangle> for RotatedRectangle {
+    #[inline]
+    fn from_cluster(regions: &[RotatedRectangle]) -> Self {
+        let scale = (regions.len() as f32).recip();
+        let first = regions[0].angle;
+
+        let mut point = Point2::origin();
+        let (mut width, mut height, mut angle) = (0.0, 0.0, 0.0);
+
+        for region in regions.iter() {
+            let delta = region.angle - first;
+
+            point += region.point.coords;
+            width += region.width;
+            height += region.height;
+            angle += delta - PI * ComplexField::round(delta / PI);
+        }
+
+        RotatedRectangle {
+            point: point * scale,
+            width: width * scale,
+            height: height * scale,
+            angle: first + angle * scale,
+        }
+    }
+}
+
 /// Clusterize detection results based on intersection and score thresholds.
 ///
 /// ### Arguments
@@ -70,9 +189,43 @@ pub fn clusterize<R: Region + Copy>(
     score_threshold: f32,
     dest: &mut Vec<Detection<Target>>,
 ) {
+    clusterize_into(data, intersection_threshold, score_threshold, dest);
+}
+
+/// Clusterize detection results keeping width and height of regions,
+/// e.g. detections of the rectangular windows.
+///
+/// See [`clusterize`] for arguments.
+#[inline]
+pub fn clusterize_rect<R: Region + Copy>(
+    data: &mut [Detection<R>],
+    intersection_threshold: f32,
+    score_threshold: f32,
+    dest: &mut Vec<Detection<Rectangle>>,
+) {
+    clusterize_into(data, intersection_threshold, score_threshold, dest);
+}
+
+/// Clusterize detection results into any region built with [`FromCluster`].
+///
+/// Detections are grouped by [`Region::overlap`], so rotated regions are
+/// grouped by their oriented intersection over union.
+///
+/// See [`clusterize`] for arguments.
+#[inline]
+pub fn clusterize_into<R, T>(
+    data: &mut [Detection<R>],
+    intersection_threshold: f32,
+    score_threshold: f32,
+    dest: &mut Vec<Detection<T>>,
+) where
+    R: Region + Copy,
+    T: Region + FromCluster<R>,
+{
     data.sort_by(|a, b| b.partial_cmp(a).unwrap());
 
     let mut assignments = vec![false; data.len()];
+    let mut cluster = Vec::new();
 
     for (i, det1) in data.iter().enumerate() {
         if assignments[i] {
@@ -81,40 +234,107 @@ pub fn clusterize<R: Region + Copy>(
             assignments[i] = true;
         }
 
-        let mut point = det1.region.top_left();
-        let mut size = det1.region.width();
+        cluster.clear();
+        cluster.push(det1.region);
 
         let mut score = det1.score;
-        let mut count: usize = 1;
 
         for (det2, j) in data[(i + 1)..].iter().zip((i + 1)..) {
-            if let Some(value) = intersection_over_union(det1.region, det2.region) {
+            if let Some(value) = det1.region.overlap(&det2.region) {
                 if value > intersection_threshold {
                     assignments[j] = true;
 
-                    point += det2.region.top_left().coords;
-                    size += det2.region.width();
+                    cluster.push(det2.region);
 
                     score += det2.score * value;
-                    count += 1;
                 }
             }
         }
 
         if score > score_threshold {
-            let scale = (count as f32).recip();
-
-            let size = (size as f32) * scale;
-
-            let mut point: Point2<f32> = point.cast();
-
-            point.coords.scale_mut(scale);
-            point.coords.add_scalar_mut(size / 2.0);
-
             dest.push(Detection {
-                region: Target { point, size },
+                region: T::from_cluster(&cluster),
                 score,
             });
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::intersection_over_union;
+
+    use super::*;
+
+    #[test]
+    fn test_clusterize_rect() {
+        let mut data = vec![
+            Detection::new(Rectangle::new(10, 20, 30, 60), 2.0),
+            Detection::new(Rectangle::new(12, 22, 30, 62), 1.0),
+            Detection::new(Rectangle::new(100, 20, 30, 60), 0.5),
+        ];
+
+        let mut dest = Vec::new();
+        clusterize_rect(&mut data, 0.5, 0.0, &mut dest);
+
+        assert_eq!(dest.len(), 2);
+        assert_eq!(dest[0].region, Rectangle::new(11, 21, 30, 61));
+        assert!(dest[0].score > 2.0 && dest[0].score < 3.0);
+        assert_eq!(dest[1].region, Rectangle::new(100, 20, 30, 60));
+        assert_abs_diff_eq!(dest[1].score, 0.5);
+
+        // square windows give the same clusters as `clusterize`
+        let mut squares = vec![
+            Detection::new(Square::new(10, 20, 30), 2.0),
+            Detection::new(Square::new(12, 22, 32), 1.0),
+        ];
+
+        let mut targets = Vec::new();
+        clusterize(&mut squares, 0.5, 0.0, &mut targets);
+
+        let mut rectangles = Vec::new();
+        clusterize_rect(&mut squares, 0.5, 0.0, &mut rectangles);
+
+        assert_eq!(targets.len(), 1);
+        assert_eq!(rectangles.len(), 1);
+        assert_abs_diff_eq!(targets[0].score, rectangles[0].score);
+        assert_eq!(Square::from(targets[0].region), Square::new(11, 21, 31));
+        assert_eq!(rectangles[0].region, Rectangle::new(11, 21, 31, 31));
+    }
+
+    #[test]
+    fn test_clusterize_rotated() {
+        let mut data = vec![
+            Detection::new(RotatedRectangle::new(50.0, 50.0, 60.0, 4.0, PI / 4.0), 2.0),
+            Detection::new(
+                RotatedRectangle::new(52.0, 52.0, 64.0, 4.0, PI / 4.0 + 0.02),
+                1.0,
+            ),
+            // parallel strip, axis-aligned bounding boxes overlap, but strips do not
+            Detection::new(RotatedRectangle::new(58.0, 42.0, 60.0, 4.0, PI / 4.0), 1.0),
+        ];
+
+        let (r1, r3) = (data[0].region, data[2].region);
+        assert!(intersection_over_union(r1, r3).unwrap() > 0.5);
+        assert!(r1.overlap(&r3).is_none());
+
+        let mut dest: Vec<Detection<RotatedRectangle>> = Vec::new();
+        Clusterizer::default()
+            .intersection_threshold(0.5)
+            .clusterize_into(&mut data, &mut dest);
+
+        assert_eq!(dest.len(), 2);
+        assert_abs_diff_eq!(dest[0].region.x(), 51.0);
+        assert_abs_diff_eq!(dest[0].region.size().0, 62.0);
+        assert_abs_diff_eq!(dest[0].region.angle(), PI / 4.0 + 0.01, epsilon = 1e-6);
+        assert_eq!(dest[1].region, r3);
+
+        // angles are averaged across the half turn
+        let cluster = [
+            RotatedRectangle::new(0.0, 0.0, 4.0, 2.0, PI / 2.0 - 0.1),
+            RotatedRectangle::new(0.0, 0.0, 4.0, 2.0, 0.1 - PI / 2.0),
+        ];
+        let region = RotatedRectangle::from_cluster(&cluster);
+        assert_abs_diff_eq!(region.angle(), PI / 2.0, epsilon = 1e-6);
+    }
+}
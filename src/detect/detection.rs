@@ -44,7 +44,7 @@ impl<R: Region> PartialEq for Detection<R> {
 
 impl<R: Region> PartialOrd for Detection<R> {
     #[inline]
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         self.score.partial_cmp(&other.score)
     }
 }
@@ -0,0 +1,191 @@
+use core::arch::x86_64::*;
+
+use crate::geometry::Square;
+
+use super::super::DetectorRef;
+use super::{Lanes, LANES};
+
+/// Contiguous 8-bit image with dimensions checked for gathers.
+#[derive(Clone, Copy)]
+pub(super) struct Bytes<'a> {
+    data: &'a [u8],
+    width: i32,
+    height: i32,
+    stride: i32,
+}
+
+impl<'a> Bytes<'a> {
+    /// Check that every pixel of `width x height` image is inside the buffer
+    /// and all byte offsets fit into `i32`.
+    #[inline]
+    pub fn new(data: &'a [u8], width: u32, height: u32, stride: usize) -> Option<Self> {
+        if width == 0 || height == 0 || stride < width as usize || data.len() < 4 {
+            return None;
+        }
+
+        let required = (height as usize - 1)
+            .checked_mul(stride)?
+            .checked_add(width as usize)?;
+
+        if data.len() < required || data.len() > i32::MAX as usize {
+            return None;
+        }
+
+        Some(Self {
+            data,
+            width: width as i32,
+            height: height as i32,
+            stride: stride as i32,
+        })
+    }
+}
+
+/// Check that the model has trees and its gather indices fit into `i32`.
+#[inline]
+pub(super) fn supports(model: &DetectorRef<'_>) -> bool {
+    !model.thresholds.is_empty() && model.predictions.len() <= i32::MAX as usize
+}
+
+/// Batched cascade on 8 lanes with SIMD offset arithmetic, pixel gathers and comparisons.
+///
+/// Lanes are managed the same way as in the portable implementation.
+///
+/// # Safety
+///
+/// CPU should support AVX2 and the model should pass [`supports`] check.
+#[target_feature(enable = "avx2")]
+pub(super) unsafe fn run_batch(
+    model: &DetectorRef<'_>,
+    depth: usize,
+    image: Bytes<'_>,
+    regions: &[Square],
+    scores: &mut [Option<f32>],
+) {
+    let tree_size = 1i32 << depth;
+    let ntrees = model.thresholds.len();
+
+    let mut lanes = Lanes::default();
+    let mut window = [0usize; LANES];
+    let mut active = [false; LANES];
+
+    let mut next = 0;
+    for l in 0..LANES {
+        // idle lanes repeat the first window to keep gathers in bounds
+        lanes.set(l, regions[next.min(regions.len() - 1)]);
+        if next < regions.len() {
+            window[l] = next;
+            active[l] = true;
+            next += 1;
+        }
+    }
+
+    let nodes = model.nodes.as_ptr() as *const i32;
+    let predictions = model.predictions.as_ptr() as *const f32;
+    let thresholds = model.thresholds.as_ptr() as *const f32;
+    let pixels = image.data.as_ptr() as *const i32;
+
+    let one = _mm256_set1_epi32(1);
+    let zero = _mm256_setzero_si256();
+    let byte = _mm256_set1_epi32(0xff);
+    let right = _mm256_set1_epi32(image.width - 1);
+    let bottom = _mm256_set1_epi32(image.height - 1);
+    let stride = _mm256_set1_epi32(image.stride);
+    let last = _mm256_set1_epi32(image.data.len() as i32 - 4);
+    let nodes_count = _mm256_set1_epi32(tree_size - 1);
+    let predictions_count = _mm256_set1_epi32(tree_size);
+
+    let ntrees = _mm256_set1_epi32(ntrees as i32);
+    let mut vtree = _mm256_setzero_si256();
+    let mut vresult = _mm256_setzero_ps();
+    let mut vx = _mm256_loadu_si256(lanes.x.as_ptr() as *const __m256i);
+    let mut vy = _mm256_loadu_si256(lanes.y.as_ptr() as *const __m256i);
+    let mut vs = _mm256_loadu_si256(lanes.s.as_ptr() as *const __m256i);
+
+    while active.contains(&true) {
+        // pixel at node offset `(nx, ny)` of each lane window clamped to the image
+        let pixel = |nx: __m256i, ny: __m256i| {
+            let px = _mm256_srai_epi32::<8>(_mm256_add_epi32(vx, _mm256_mullo_epi32(nx, vs)));
+            let py = _mm256_srai_epi32::<8>(_mm256_add_epi32(vy, _mm256_mullo_epi32(ny, vs)));
+
+            let px = _mm256_min_epi32(_mm256_max_epi32(px, zero), right);
+            let py = _mm256_min_epi32(_mm256_max_epi32(py, zero), bottom);
+
+            // gather 4 bytes not crossing the buffer end and shift the pixel down
+            let offset = _mm256_add_epi32(_mm256_mullo_epi32(py, stride), px);
+            let start = _mm256_min_epi32(offset, last);
+            let shift = _mm256_slli_epi32::<3>(_mm256_sub_epi32(offset, start));
+            let word = _mm256_i32gather_epi32::<1>(pixels, start);
+
+            _mm256_and_si256(_mm256_srlv_epi32(word, shift), byte)
+        };
+
+        let base = _mm256_sub_epi32(_mm256_mullo_epi32(vtree, nodes_count), one);
+        let mut idx = one;
+
+        for _ in 0..depth {
+            let node = _mm256_i32gather_epi32::<4>(nodes, _mm256_add_epi32(base, idx));
+
+            // node bytes are `[y0, x0, y1, x1]` of `i8`
+            let y0 = _mm256_srai_epi32::<24>(_mm256_slli_epi32::<24>(node));
+            let x0 = _mm256_srai_epi32::<24>(_mm256_slli_epi32::<16>(node));
+            let y1 = _mm256_srai_epi32::<24>(_mm256_slli_epi32::<8>(node));
+            let x1 = _mm256_srai_epi32::<24>(node);
+
+            // `2 * idx + 1` minus one if the first pixel is brighter
+            let brighter = _mm256_cmpgt_epi32(pixel(x0, y0), pixel(x1, y1));
+            idx = _mm256_add_epi32(_mm256_add_epi32(_mm256_slli_epi32::<1>(idx), one), brighter);
+        }
+
+        let offset = _mm256_sub_epi32(
+            _mm256_add_epi32(_mm256_mullo_epi32(vtree, predictions_count), idx),
+            predictions_count,
+        );
+        let prediction = _mm256_i32gather_ps::<4>(predictions, offset);
+        let threshold = _mm256_i32gather_ps::<4>(thresholds, vtree);
+
+        vresult = _mm256_add_ps(vresult, prediction);
+        vtree = _mm256_add_epi32(vtree, one);
+
+        let rejected = _mm256_castps_si256(_mm256_cmp_ps::<_CMP_LT_OQ>(vresult, threshold));
+        let finished = _mm256_cmpeq_epi32(vtree, ntrees);
+        let done = _mm256_movemask_ps(_mm256_castsi256_ps(_mm256_or_si256(rejected, finished)));
+
+        // most of the time all lanes keep walking the cascade
+        if done == 0 {
+            continue;
+        }
+
+        let rejected = _mm256_movemask_ps(_mm256_castsi256_ps(rejected));
+        let mut tree = [0i32; LANES];
+        let mut result = [0.0f32; LANES];
+        _mm256_storeu_si256(tree.as_mut_ptr() as *mut __m256i, vtree);
+        _mm256_storeu_ps(result.as_mut_ptr(), vresult);
+
+        for l in (0..LANES).filter(|l| done & (1 << l) != 0) {
+            // idle lanes start over from the first tree to keep gathers in bounds
+            let score = result[l] - model.threshold;
+            tree[l] = 0;
+            result[l] = 0.0;
+
+            if !active[l] {
+                continue;
+            }
+
+            scores[window[l]] = (rejected & (1 << l) == 0).then_some(score);
+
+            if next < regions.len() {
+                lanes.set(l, regions[next]);
+                window[l] = next;
+                next += 1;
+            } else {
+                active[l] = false;
+            }
+        }
+
+        vtree = _mm256_loadu_si256(tree.as_ptr() as *const __m256i);
+        vresult = _mm256_loadu_ps(result.as_ptr());
+        vx = _mm256_loadu_si256(lanes.x.as_ptr() as *const __m256i);
+        vy = _mm256_loadu_si256(lanes.y.as_ptr() as *const __m256i);
+        vs = _mm256_loadu_si256(lanes.s.as_ptr() as *const __m256i);
+    }
+}
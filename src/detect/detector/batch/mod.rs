@@ -0,0 +1,413 @@
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+mod avx2;
+
+use crate::geometry::Square;
+use crate::traits::{GrayImageView, Region};
+
+use super::DetectorRef;
+
+/// Number of windows classified together by batched classification.
+const LANES: usize = 8;
+
+/// Window coordinates of a batch lane by lane, so the offset arithmetic
+/// can be vectorized by the compiler.
+#[derive(Clone, Copy, Default)]
+struct Lanes {
+    x: [i32; LANES],
+    y: [i32; LANES],
+    s: [i32; LANES],
+}
+
+impl Lanes {
+    /// Whether node offsets of the window are computed in `i32` without overflow.
+    #[inline]
+    fn fits(region: &Square) -> bool {
+        let center = region.center();
+        let size = region.size() as i64;
+
+        [center.x, center.y].iter().all(|&value| {
+            let value = (value as i64) << 8;
+            value - 128 * size >= i32::MIN as i64 && value + 127 * size <= i32::MAX as i64
+        })
+    }
+
+    /// Load center and size of the region into the lane.
+    #[inline(always)]
+    fn set(&mut self, lane: usize, region: Square) {
+        let center = region.center();
+        self.x[lane] = center.x << 8;
+        self.y[lane] = center.y << 8;
+        self.s[lane] = region.size() as i32;
+    }
+
+    /// Transform node offsets into the image coordinates of each lane.
+    #[inline(always)]
+    fn transform(&self, x: [i32; LANES], y: [i32; LANES]) -> ([i32; LANES], [i32; LANES]) {
+        let mut tx = [0; LANES];
+        let mut ty = [0; LANES];
+
+        for l in 0..LANES {
+            tx[l] = (self.x[l] + x[l] * self.s[l]) >> 8;
+            ty[l] = (self.y[l] + y[l] * self.s[l]) >> 8;
+        }
+
+        (tx, ty)
+    }
+}
+
+impl DetectorRef<'_> {
+    /// Estimate detection scores for many regions at once.
+    ///
+    /// Regions are classified in groups of 8 walking the cascade together,
+    /// which is faster than [`classify`](Self::classify) for each region, e.g. for
+    /// a row of windows of the same size. Results are the same as of
+    /// [`classify`](Self::classify) for each region, windows too large
+    /// for the batched offset arithmetic are classified one by one.
+    ///
+    /// ### Arguments
+    ///
+    /// * `image` -- image to classify regions on;
+    /// * `regions` -- regions to classify;
+    /// * `scores` -- output scores, `None` for rejected regions.
+    ///
+    /// ### Panics
+    ///
+    /// If `regions` and `scores` lengths differ.
+    #[inline]
+    pub fn classify_batch<I>(&self, image: &I, regions: &[Square], scores: &mut [Option<f32>])
+    where
+        I: GrayImageView,
+    {
+        self.run_batch(self.depth, image, regions, scores)
+    }
+
+    /// Batched version of [`run`](Self::run).
+    #[inline(always)]
+    pub(super) fn run_batch<I>(
+        &self,
+        depth: usize,
+        image: &I,
+        regions: &[Square],
+        scores: &mut [Option<f32>],
+    ) where
+        I: GrayImageView,
+    {
+        assert_eq!(regions.len(), scores.len());
+
+        let mut start = 0;
+
+        for (i, region) in regions.iter().enumerate() {
+            if !Lanes::fits(region) {
+                self.dispatch(depth, image, &regions[start..i], &mut scores[start..i]);
+                scores[i] = self.run(depth, image, region);
+                start = i + 1;
+            }
+        }
+
+        self.dispatch(depth, image, &regions[start..], &mut scores[start..])
+    }
+
+    /// Dispatches windows fitting into [`Lanes`] to AVX2 gathers
+    /// for contiguous 8-bit images if the CPU supports it.
+    #[inline(always)]
+    fn dispatch<I>(&self, depth: usize, image: &I, regions: &[Square], scores: &mut [Option<f32>])
+    where
+        I: GrayImageView,
+    {
+        if regions.is_empty() {
+            return;
+        }
+
+        #[cfg(all(feature = "std", target_arch = "x86_64"))]
+        if self.border == crate::BorderMode::Clamp
+            && std::arch::is_x86_feature_detected!("avx2")
+            && avx2::supports(self)
+        {
+            let (width, height) = image.size();
+            let bytes = image.buffer().and_then(|(data, stride)| {
+                crate::Luminance::as_bytes(data)
+                    .and_then(|data| avx2::Bytes::new(data, width, height, stride))
+            });
+
+            if let Some(bytes) = bytes {
+                // SAFETY: AVX2 support and the model are checked above,
+                // the image buffer is checked by `Bytes::new`.
+                return unsafe { avx2::run_batch(self, depth, bytes, regions, scores) };
+            }
+        }
+
+        self.run_lanes(depth, image, regions, scores)
+    }
+
+    /// Each lane walks the cascade on its own window and takes
+    /// the next pending window as soon as the current one is done,
+    /// so lanes are not idle when windows are rejected at different trees.
+    #[inline(always)]
+    fn run_lanes<I>(&self, depth: usize, image: &I, regions: &[Square], scores: &mut [Option<f32>])
+    where
+        I: GrayImageView,
+    {
+        let tree_size = 1 << depth;
+        let ntrees = self.thresholds.len();
+
+        let mut lanes = Lanes::default();
+        let mut window = [0usize; LANES];
+        let mut tree = [0usize; LANES];
+        let mut result = [0.0f32; LANES];
+        let mut active = [false; LANES];
+        // lanes having a sample outside of the image with `BorderMode::Reject`
+        let mut outside = [false; LANES];
+
+        let mut next = 0;
+        for l in 0..LANES.min(regions.len()) {
+            lanes.set(l, regions[next]);
+            window[l] = next;
+            active[l] = true;
+            next += 1;
+        }
+
+        while active.contains(&true) {
+            let mut idx = [1usize; LANES];
+
+            for _ in 0..depth {
+                let mut x0 = [0; LANES];
+                let mut y0 = [0; LANES];
+                let mut x1 = [0; LANES];
+                let mut y1 = [0; LANES];
+
+                for l in 0..LANES {
+                    let [ny0, nx0, ny1, nx1] = self.nodes[tree[l] * (tree_size - 1) + idx[l] - 1];
+                    y0[l] = ny0 as i8 as i32;
+                    x0[l] = nx0 as i8 as i32;
+                    y1[l] = ny1 as i8 as i32;
+                    x1[l] = nx1 as i8 as i32;
+                }
+
+                let (x0, y0) = lanes.transform(x0, y0);
+                let (x1, y1) = lanes.transform(x1, y1);
+
+                for l in 0..LANES {
+                    let p0 = image.luma_border([x0[l], y0[l]].into(), self.border);
+                    let p1 = image.luma_border([x1[l], y1[l]].into(), self.border);
+                    let brighter = match (p0, p1) {
+                        (Some(p0), Some(p1)) => p0 > p1,
+                        _ => {
+                            outside[l] = true;
+                            false
+                        }
+                    };
+                    idx[l] = 2 * idx[l] + !brighter as usize;
+                }
+            }
+
+            for l in 0..LANES {
+                if !active[l] {
+                    continue;
+                }
+
+                let offset = tree[l] * tree_size + idx[l] - tree_size;
+                result[l] += f32::from_le_bytes(self.predictions[offset]);
+
+                let rejected =
+                    outside[l] || result[l] < f32::from_le_bytes(self.thresholds[tree[l]]);
+                tree[l] += 1;
+
+                if !rejected && tree[l] < ntrees {
+                    continue;
+                }
+
+                scores[window[l]] = (!rejected).then(|| result[l] - self.threshold);
+                outside[l] = false;
+
+                if next < regions.len() {
+                    lanes.set(l, regions[next]);
+                    window[l] = next;
+                    tree[l] = 0;
+                    result[l] = 0.0;
+                    next += 1;
+                } else {
+                    // idle lanes keep walking the first tree until all lanes are done
+                    active[l] = false;
+                    tree[l] = 0;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use image::{GrayImage, ImageBuffer, Luma};
+    use rand::{Rng, SeedableRng};
+    use rand_xoshiro::Xoroshiro128PlusPlus;
+
+    use crate::detect::random_detector;
+    use crate::view::GrayView;
+    use crate::BorderMode;
+
+    use super::*;
+
+    fn random_regions(rng: &mut impl Rng, count: usize) -> Vec<Square> {
+        // some regions are partially outside of the image
+        (0..count)
+            .map(|_| {
+                Square::new(
+                    rng.random_range(-16..64),
+                    rng.random_range(-16..48),
+                    rng.random_range(4..40),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_classify_batch() {
+        let mut rng = Xoroshiro128PlusPlus::seed_from_u64(42);
+        let detector = random_detector(&mut rng, 4, 16);
+        let image = GrayImage::from_fn(64, 48, |_, _| Luma([rng.random()]));
+
+        // batch size is not a multiple of the lanes count
+        let regions = random_regions(&mut rng, 1003);
+        let expected: Vec<_> = regions
+            .iter()
+            .map(|region| detector.classify(&image, *region))
+            .collect();
+
+        let accepted = expected.iter().flatten().count();
+        assert!(accepted > 0 && accepted < regions.len());
+
+        let mut scores = vec![None; regions.len()];
+        detector.classify_batch(&image, &regions, &mut scores);
+        assert_eq!(scores, expected);
+
+        let mut scores = vec![Some(0.0); regions.len()];
+        detector
+            .view()
+            .run_lanes(detector.depth, &image, &regions, &mut scores);
+        assert_eq!(scores, expected);
+
+        for count in [0, 1, 7, 9] {
+            let mut scores = vec![None; count];
+            detector.classify_batch(&image, &regions[..count], &mut scores);
+            assert_eq!(scores, expected[..count]);
+        }
+    }
+
+    #[test]
+    fn test_classify_batch_views() {
+        let mut rng = Xoroshiro128PlusPlus::seed_from_u64(42);
+        let detector = random_detector(&mut rng, 3, 24);
+        let image = GrayImage::from_fn(64, 48, |_, _| Luma([rng.random()]));
+        let regions = random_regions(&mut rng, 256);
+
+        let expected: Vec<_> = regions
+            .iter()
+            .map(|region| detector.classify(&image, *region))
+            .collect();
+
+        // rows are padded and the last row is not
+        let stride = 70;
+        let mut data = vec![0u8; 47 * stride + 64];
+        for (x, y, pixel) in image.enumerate_pixels() {
+            data[y as usize * stride + x as usize] = pixel.0[0];
+        }
+        let view = GrayView::new(&data, 64, 48, stride).unwrap();
+
+        let mut scores = vec![None; regions.len()];
+        detector.classify_batch(&view, &regions, &mut scores);
+        assert_eq!(scores, expected);
+
+        let wide = ImageBuffer::<Luma<u16>, _>::from_fn(64, 48, |x, y| {
+            Luma([image.get_pixel(x, y).0[0] as u16 * 257])
+        });
+
+        let mut scores = vec![None; regions.len()];
+        detector.classify_batch(&wide, &regions, &mut scores);
+        assert_eq!(scores, expected);
+    }
+
+    #[test]
+    fn test_classify_batch_border() {
+        let mut rng = Xoroshiro128PlusPlus::seed_from_u64(42);
+        let mut detector = random_detector(&mut rng, 3, 16);
+        let image = GrayImage::from_fn(64, 48, |_, _| Luma([rng.random()]));
+        let regions = random_regions(&mut rng, 512);
+
+        let clamped: Vec<_> = regions
+            .iter()
+            .map(|region| detector.classify(&image, *region))
+            .collect();
+
+        for border in [
+            BorderMode::ZERO,
+            BorderMode::Constant(255),
+            BorderMode::Reflect,
+            BorderMode::Wrap,
+            BorderMode::Reject,
+        ] {
+            detector.set_border(border);
+
+            let expected: Vec<_> = regions
+                .iter()
+                .map(|region| detector.classify(&image, *region))
+                .collect();
+
+            let mut scores = vec![None; regions.len()];
+            detector.classify_batch(&image, &regions, &mut scores);
+            assert_eq!(scores, expected, "{:?}", border);
+        }
+
+        // windows accepted with rejecting border mode are sampled inside the image
+        let mut scores = vec![None; regions.len()];
+        detector.classify_batch(&image, &regions, &mut scores);
+
+        let accepted = scores.iter().flatten().count();
+        assert!(accepted > 0 && accepted < clamped.iter().flatten().count());
+
+        for (score, expected) in scores.iter().zip(clamped.iter()) {
+            if score.is_some() {
+                assert_eq!(score, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_classify_batch_huge_windows() {
+        let mut rng = Xoroshiro128PlusPlus::seed_from_u64(42);
+        let mut detector = random_detector(&mut rng, 3, 16);
+        let image = GrayImage::from_fn(64, 48, |_, _| Luma([rng.random()]));
+
+        // offsets of windows in between overflow `i32`
+        let mut regions = random_regions(&mut rng, 32);
+        regions.insert(3, Square::new(-(1 << 23), 3, 1 << 24));
+        regions.insert(11, Square::new(5, i32::MAX - 300, 200));
+        regions.insert(12, Square::new(i32::MIN + 10, -20, 1 << 20));
+        regions.push(Square::new(-12_582_913, 3, 1 << 24));
+
+        assert!(!regions.iter().all(Lanes::fits));
+
+        for border in [BorderMode::Clamp, BorderMode::Reject] {
+            detector.set_border(border);
+
+            let expected: Vec<_> = regions
+                .iter()
+                .map(|region| detector.classify(&image, *region))
+                .collect();
+
+            let mut scores = vec![None; regions.len()];
+            detector.classify_batch(&image, &regions, &mut scores);
+            assert_eq!(scores, expected, "{:?}", border);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_classify_batch_lengths() {
+        let mut rng = Xoroshiro128PlusPlus::seed_from_u64(42);
+        let detector = random_detector(&mut rng, 2, 2);
+        let image = GrayImage::new(16, 16);
+
+        let mut scores = vec![None; 2];
+        detector.classify_batch(&image, &[Square::new(0, 0, 8)], &mut scores);
+    }
+}
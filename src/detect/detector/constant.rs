@@ -0,0 +1,165 @@
+use crate::border::BorderMode;
+use crate::geometry::Square;
+use crate::traits::{GrayImageView, Region};
+
+use super::{Detection, DetectorRef};
+
+/// Detector model with tree depth known at compile time.
+///
+/// It is meant to be stored in a `static` generated by
+/// [`codegen::detector`](crate::codegen::detector), so classification
+/// requires no heap allocation and tree traversal is unrolled by the compiler.
+#[derive(Debug, Clone, Copy)]
+pub struct StaticDetector<const DEPTH: usize> {
+    model: DetectorRef<'static>,
+}
+
+impl<const DEPTH: usize> StaticDetector<DEPTH> {
+    /// Create a detector from flat model arrays.
+    ///
+    /// ### Arguments
+    ///
+    /// * `nodes` -- `ntrees * (2^DEPTH - 1)` comparison nodes as `[y0, x0, y1, x1]` bytes;
+    /// * `predictions` -- `ntrees * 2^DEPTH` leaf predictions as `f32` little-endian bytes;
+    /// * `thresholds` -- `ntrees` tree thresholds as `f32` little-endian bytes.
+    ///
+    /// ### Panics
+    ///
+    /// If array lengths do not match each other (at compile time in `const` context).
+    pub const fn new(
+        nodes: &'static [[u8; 4]],
+        predictions: &'static [[u8; 4]],
+        thresholds: &'static [[u8; 4]],
+    ) -> Self {
+        let ntrees = thresholds.len();
+        assert!(ntrees > 0, "no trees");
+        assert!(
+            nodes.len() == ntrees * ((1 << DEPTH) - 1),
+            "invalid nodes count"
+        );
+        assert!(
+            predictions.len() == ntrees << DEPTH,
+            "invalid predictions count"
+        );
+
+        Self {
+            model: DetectorRef {
+                depth: DEPTH,
+                threshold: f32::from_le_bytes(thresholds[ntrees - 1]),
+                border: BorderMode::Clamp,
+                nodes,
+                predictions,
+                thresholds,
+            },
+        }
+    }
+
+    /// Returns a borrowed view of the detector model.
+    #[inline]
+    pub const fn view(&self) -> DetectorRef<'static> {
+        self.model
+    }
+
+    /// Returns the detector with the handling of samples outside of the image.
+    #[inline]
+    pub const fn with_border(self, border: BorderMode) -> Self {
+        let mut model = self.model;
+        model.border = border;
+        Self { model }
+    }
+
+    /// Estimate detection score for the rectangular region.
+    ///
+    /// See [`Detector::classify`](super::Detector::classify).
+    #[inline]
+    pub fn classify<I, R>(&self, image: &I, region: R) -> Option<f32>
+    where
+        I: GrayImageView,
+        R: Region,
+    {
+        self.model.run(DEPTH, image, &region)
+    }
+
+    /// Estimate detection scores for many regions at once.
+    ///
+    /// See [`DetectorRef::classify_batch`].
+    #[inline]
+    pub fn classify_batch<I>(&self, image: &I, regions: &[Square], scores: &mut [Option<f32>])
+    where
+        I: GrayImageView,
+    {
+        self.model.run_batch(DEPTH, image, regions, scores)
+    }
+
+    /// Detect an object in the rectangular region.
+    #[inline]
+    pub fn detect<I, R>(&self, image: &I, region: R) -> Option<Detection<R>>
+    where
+        I: GrayImageView,
+        R: Region,
+    {
+        self.model
+            .run(DEPTH, image, &region)
+            .map(|score| Detection { region, score })
+    }
+}
+
+impl<const DEPTH: usize> From<&StaticDetector<DEPTH>> for DetectorRef<'static> {
+    #[inline]
+    fn from(detector: &StaticDetector<DEPTH>) -> Self {
+        detector.model
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use image::{GrayImage, Luma};
+
+    use crate::detect::model_bytes;
+    use crate::Detector;
+
+    use super::*;
+
+    const fn f32s<const N: usize>(values: [f32; N]) -> [[u8; 4]; N] {
+        let mut result = [[0u8; 4]; N];
+        let mut i = 0;
+        while i < N {
+            result[i] = values[i].to_le_bytes();
+            i += 1;
+        }
+        result
+    }
+
+    static MODEL: StaticDetector<1> = StaticDetector::new(
+        &[[0, 129, 0, 127], [0, 129, 0, 127]],
+        &f32s([1.0, -1.0, 2.0, -2.0]),
+        &f32s([0.0, 0.5]),
+    );
+
+    #[test]
+    fn test_static_detector_classify() {
+        let data = model_bytes(
+            1,
+            [
+                (vec![[0, 129, 0, 127]], vec![1.0, -1.0], 0.0),
+                (vec![[0, 129, 0, 127]], vec![2.0, -2.0], 0.5),
+            ],
+        );
+        let detector = Detector::load(data.as_slice()).unwrap();
+
+        let image = GrayImage::from_fn(8, 8, |x, _| Luma([if x < 4 { 200 } else { 0 }]));
+
+        for square in [
+            Square::new(0, 0, 4),
+            Square::new(2, 2, 4),
+            Square::new(4, 4, 4),
+        ] {
+            assert_eq!(
+                MODEL.classify(&image, square),
+                detector.classify(&image, square)
+            );
+        }
+        assert_eq!(MODEL.classify(&image, Square::new(1, 1, 4)), Some(2.5));
+        assert_eq!(MODEL.classify(&image, Square::new(4, 4, 4)), None);
+    }
+}
@@ -0,0 +1,397 @@
+use core::convert::TryInto;
+use core::fmt::Debug;
+#[cfg(feature = "std")]
+use std::io::Write;
+
+use nalgebra::Point2;
+
+use crate::io::{Error, ErrorKind};
+
+use crate::border::BorderMode;
+use crate::nodes::ComparisonNode;
+use crate::traits::GrayImageView;
+use crate::traits::Region;
+
+use super::{Detection, Detector};
+
+/// Magic bytes of the flat detector model format.
+const MAGIC: &[u8; 8] = b"PICOFLAT";
+
+/// Borrowed flat [`Detector`] model.
+///
+/// It can be created without copying from the flat model bytes, for example
+/// `&'static [u8]` embedded with `include_bytes!` or a memory-mapped file.
+///
+/// Flat format (all values are little-endian):
+///
+/// * magic `PICOFLAT`;
+/// * `depth` and `ntrees` as `u32`;
+/// * `ntrees * (2^depth - 1)` comparison nodes as `[y0, x0, y1, x1]` of `i8`;
+/// * `ntrees * 2^depth` leaf predictions as `f32`;
+/// * `ntrees` tree thresholds as `f32`.
+///
+/// Use [`Detector::write_flat`] to convert a model into this format.
+/// Border mode is not a part of the model, it is [`BorderMode::Clamp`] after loading.
+#[derive(Clone, Copy)]
+pub struct DetectorRef<'a> {
+    pub(crate) depth: usize,
+    pub(crate) threshold: f32,
+    pub(crate) border: BorderMode,
+    pub(crate) nodes: &'a [[u8; 4]],
+    pub(crate) predictions: &'a [[u8; 4]],
+    pub(crate) thresholds: &'a [[u8; 4]],
+}
+
+impl Debug for DetectorRef<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct(stringify!(DetectorRef))
+            .field("depth", &self.depth)
+            .field("threshold", &self.threshold)
+            .field("border", &self.border)
+            .field("trees", &self.thresholds.len())
+            .finish()
+    }
+}
+
+impl<'a> DetectorRef<'a> {
+    /// Borrow a detector model from the flat format bytes.
+    pub fn from_bytes(data: &'a [u8]) -> Result<Self, Error> {
+        if data.len() < 16 {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+
+        let (header, data) = data.split_at(16);
+
+        if &header[..8] != MAGIC {
+            return Err(Error::new(ErrorKind::InvalidData, "invalid magic"));
+        }
+
+        let mut fields = header[8..]
+            .chunks_exact(4)
+            .map(|field| u32::from_le_bytes(field.try_into().unwrap()));
+        let depth = fields.next().unwrap();
+        let ntrees = fields.next().unwrap() as usize;
+
+        if ntrees == 0 {
+            return Err(Error::other("No trees"));
+        }
+
+        let overflow = || Error::other("depth overflow");
+        let tree_size = 2usize.checked_pow(depth).ok_or_else(overflow)?;
+        let nnodes = ntrees.checked_mul(tree_size - 1).ok_or_else(overflow)?;
+        let npredictions = ntrees.checked_mul(tree_size).ok_or_else(overflow)?;
+
+        let chunks = data.chunks_exact(4);
+
+        if !chunks.remainder().is_empty() || chunks.len() != nnodes + npredictions + ntrees {
+            return Err(Error::new(ErrorKind::InvalidData, "invalid model size"));
+        }
+
+        // SAFETY: `[u8; 4]` has the alignment of `u8` and `data` holds exactly `chunks.len()` of them.
+        let values: &[[u8; 4]] =
+            unsafe { core::slice::from_raw_parts(data.as_ptr().cast(), chunks.len()) };
+
+        let (nodes, values) = values.split_at(nnodes);
+        let (predictions, thresholds) = values.split_at(npredictions);
+
+        Ok(Self {
+            depth: depth as usize,
+            threshold: f32::from_le_bytes(thresholds[ntrees - 1]),
+            border: BorderMode::Clamp,
+            nodes,
+            predictions,
+            thresholds,
+        })
+    }
+
+    /// Returns the handling of samples outside of the image.
+    #[inline]
+    pub fn border(&self) -> BorderMode {
+        self.border
+    }
+
+    /// Returns the model with the handling of samples outside of the image.
+    #[inline]
+    pub fn with_border(self, border: BorderMode) -> Self {
+        Self { border, ..self }
+    }
+
+    /// Estimate detection score for the rectangular region.
+    ///
+    /// See [`Detector::classify`].
+    #[inline]
+    pub fn classify<I, R>(&self, image: &I, region: R) -> Option<f32>
+    where
+        I: GrayImageView,
+        R: Region,
+    {
+        self.run(self.depth, image, &region)
+    }
+
+    /// Runs the cascade with the specified tree depth, so callers knowing
+    /// the depth at compile time get the tree traversal unrolled.
+    ///
+    /// Windows inside the image are sampled without bounds checks.
+    #[inline(always)]
+    pub(super) fn run<I, R>(&self, depth: usize, image: &I, region: &R) -> Option<f32>
+    where
+        I: GrayImageView,
+        R: Region,
+    {
+        let scale = (region.width(), region.height());
+
+        if ComparisonNode::interior(image.size(), region.center(), scale) {
+            // SAFETY: all node offsets of the window are inside the image.
+            unsafe { self.run_interior(depth, image, region) }
+        } else {
+            self.run_border(depth, image, region)
+        }
+    }
+
+    /// Runs the cascade sampling pixels outside of the image with the border mode.
+    #[inline(always)]
+    pub(crate) fn run_border<I, R>(&self, depth: usize, image: &I, region: &R) -> Option<f32>
+    where
+        I: GrayImageView,
+        R: Region,
+    {
+        match self.border {
+            BorderMode::Clamp => self.cascade(depth, region, |node, point, scale| {
+                Some(node.bintest(image, point, scale))
+            }),
+            border => self.cascade(depth, region, |node, point, scale| {
+                node.bintest_border(image, point, scale, border)
+            }),
+        }
+    }
+
+    /// Runs the cascade sampling pixels without bounds checks.
+    ///
+    /// # Safety
+    ///
+    /// The window should be inside the image, see [`ComparisonNode::interior`].
+    #[inline(always)]
+    pub(crate) unsafe fn run_interior<I, R>(
+        &self,
+        depth: usize,
+        image: &I,
+        region: &R,
+    ) -> Option<f32>
+    where
+        I: GrayImageView,
+        R: Region,
+    {
+        self.cascade(depth, region, |node, point, scale| {
+            Some(node.bintest_unchecked(image, point, scale))
+        })
+    }
+
+    /// Walks the trees with `bintest` returning `None` to reject the window.
+    #[inline(always)]
+    fn cascade<R, F>(&self, depth: usize, region: &R, bintest: F) -> Option<f32>
+    where
+        R: Region,
+        F: Fn(ComparisonNode, Point2<i32>, (u32, u32)) -> Option<bool>,
+    {
+        let tree_size = 1 << depth;
+        let mut result = 0.0f32;
+        let point = region.center();
+        let scale = (region.width(), region.height());
+
+        for (tree, threshold) in self.thresholds.iter().enumerate() {
+            let nodes = &self.nodes[tree * (tree_size - 1)..];
+            let mut idx = 1;
+
+            for _ in 0..depth {
+                let node = ComparisonNode::from(nodes[idx - 1]);
+                idx = 2 * idx + !bintest(node, point, scale)? as usize;
+            }
+            result += f32::from_le_bytes(self.predictions[tree * tree_size + idx - tree_size]);
+
+            if result < f32::from_le_bytes(*threshold) {
+                return None;
+            }
+        }
+        Some(result - self.threshold)
+    }
+
+    /// Detect an object in the rectangular region.
+    #[inline]
+    pub fn detect<I, R>(&self, image: &I, region: R) -> Option<Detection<R>>
+    where
+        I: GrayImageView,
+        R: Region,
+    {
+        self.run(self.depth, image, &region)
+            .map(|score| Detection { region, score })
+    }
+
+    /// Write the detector model in the flat format.
+    #[cfg(feature = "std")]
+    pub fn write_flat(&self, mut writable: impl Write) -> Result<(), Error> {
+        writable.write_all(MAGIC)?;
+        writable.write_all(&(self.depth as u32).to_le_bytes())?;
+        writable.write_all(&(self.thresholds.len() as u32).to_le_bytes())?;
+
+        for value in self
+            .nodes
+            .iter()
+            .chain(self.predictions)
+            .chain(self.thresholds)
+        {
+            writable.write_all(value)?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn tree_size(&self) -> usize {
+        1 << self.depth
+    }
+
+    /// Comparison node of the tree at the index starting from `1`.
+    #[cfg(test)]
+    pub(super) fn node(&self, tree: usize, idx: usize) -> ComparisonNode {
+        ComparisonNode::from(self.nodes[tree * (self.tree_size() - 1) + idx - 1])
+    }
+
+    #[cfg(test)]
+    pub(super) fn prediction(&self, tree: usize, idx: usize) -> f32 {
+        f32::from_le_bytes(self.predictions[tree * self.tree_size() + idx])
+    }
+
+    #[cfg(test)]
+    pub(super) fn tree_threshold(&self, tree: usize) -> f32 {
+        f32::from_le_bytes(self.thresholds[tree])
+    }
+}
+
+impl From<DetectorRef<'_>> for Detector {
+    #[inline]
+    fn from(model: DetectorRef<'_>) -> Self {
+        Self {
+            depth: model.depth,
+            threshold: model.threshold,
+            border: model.border,
+            nodes: model.nodes.to_vec(),
+            predictions: model.predictions.to_vec(),
+            thresholds: model.thresholds.to_vec(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use image::{GrayImage, ImageBuffer, Luma};
+    use rand::{Rng, SeedableRng};
+    use rand_xoshiro::Xoroshiro128PlusPlus;
+
+    use crate::detect::random_detector;
+    use crate::geometry::{Rectangle, Square};
+
+    use super::*;
+
+    #[test]
+    fn test_flat_round_trip() {
+        let mut rng = Xoroshiro128PlusPlus::seed_from_u64(42);
+        let detector = random_detector(&mut rng, 3, 8);
+
+        let mut data = Vec::new();
+        detector.write_flat(&mut data).unwrap();
+        assert_eq!(data.len(), 16 + 8 * (7 + 8 + 1) * 4);
+
+        let model = DetectorRef::from_bytes(&data).unwrap();
+        let image = GrayImage::from_fn(64, 64, |_, _| Luma([rng.random()]));
+
+        let mut detected = 0;
+        for _ in 0..256 {
+            let square = Square::new(rng.random_range(0..48), rng.random_range(0..48), 16);
+            let score = detector.classify(&image, square);
+            detected += score.is_some() as usize;
+            assert_eq!(score, model.classify(&image, square));
+        }
+        assert!(detected > 0);
+
+        let mut copy = Vec::new();
+        Detector::from(model).write_flat(&mut copy).unwrap();
+        assert_eq!(data, copy);
+    }
+
+    #[test]
+    fn test_classify_luminance_types() {
+        let mut rng = Xoroshiro128PlusPlus::seed_from_u64(42);
+        let detector = random_detector(&mut rng, 3, 8);
+
+        let image = GrayImage::from_fn(64, 64, |_, _| Luma([rng.random()]));
+        let wide = ImageBuffer::<Luma<u16>, _>::from_fn(64, 64, |x, y| {
+            Luma([image.get_pixel(x, y).0[0] as u16 * 257])
+        });
+        let float = ImageBuffer::<Luma<f32>, _>::from_fn(64, 64, |x, y| {
+            Luma([image.get_pixel(x, y).0[0] as f32 / 255.0])
+        });
+
+        for _ in 0..256 {
+            let square = Square::new(rng.random_range(0..48), rng.random_range(0..48), 16);
+            let score = detector.classify(&image, square);
+            assert_eq!(score, detector.classify(&wide, square));
+            assert_eq!(score, detector.classify(&float, square));
+        }
+    }
+
+    #[test]
+    fn test_classify_rectangle() {
+        let mut rng = Xoroshiro128PlusPlus::seed_from_u64(42);
+        let detector = random_detector(&mut rng, 3, 8);
+
+        let image = GrayImage::from_fn(64, 64, |_, _| Luma([rng.random()]));
+        // each row is repeated twice, so windows twice as tall see the same pixels
+        let tall = GrayImage::from_fn(64, 128, |x, y| *image.get_pixel(x, y / 2));
+
+        let mut detected = 0;
+        for _ in 0..256 {
+            let size = 2 * rng.random_range(4..24);
+            let square = Square::new(rng.random_range(-8..56), rng.random_range(-8..56), size);
+            let score = detector.classify(&image, square);
+            detected += score.is_some() as usize;
+
+            assert_eq!(score, detector.classify(&image, Rectangle::from(square)));
+
+            let rectangle = Rectangle::new(square.left(), 2 * square.top() + 1, size, 2 * size);
+            assert_eq!(score, detector.classify(&tall, rectangle));
+
+            let detection = detector.detect(&tall, rectangle);
+            assert_eq!(detection.map(|d| *d.region()), score.map(|_| rectangle));
+        }
+        assert!(detected > 0);
+    }
+
+    #[test]
+    fn test_classify_huge_window() {
+        let mut rng = Xoroshiro128PlusPlus::seed_from_u64(42);
+        let mut detector = random_detector(&mut rng, 3, 8);
+        detector.set_border(BorderMode::Reject);
+
+        // node offsets of the window overflow `i32` and wrap into the image
+        let image = GrayImage::new(4_200_000, 10);
+        let rectangle = Rectangle::new(-12_582_913, 3, 1 << 24, 2);
+
+        assert_eq!(detector.classify(&image, rectangle), None);
+        assert_eq!(detector.view().classify(&image, rectangle), None);
+    }
+
+    #[test]
+    fn test_flat_invalid() {
+        let mut rng = Xoroshiro128PlusPlus::seed_from_u64(42);
+        let detector = random_detector(&mut rng, 2, 2);
+
+        let mut data = Vec::new();
+        detector.write_flat(&mut data).unwrap();
+
+        assert!(DetectorRef::from_bytes(&data[..data.len() - 4]).is_err());
+        assert!(DetectorRef::from_bytes(&data[..10]).is_err());
+
+        data[0] = b'X';
+        assert!(DetectorRef::from_bytes(&data).is_err());
+    }
+}
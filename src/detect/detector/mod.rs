@@ -1,80 +1,120 @@
-mod tree;
+mod batch;
+mod constant;
+mod flat;
 
-use std::fmt::Debug;
-use std::io::{Error, Read};
+use alloc::vec::Vec;
+use core::fmt::Debug;
+#[cfg(feature = "std")]
+use std::io::Write;
 
-use image::Luma;
-use pixelutil_image::ExtendedImageView;
+use crate::io::{Error, Read};
 
+use crate::border::BorderMode;
 use crate::geometry::Square;
-use crate::traits::Region;
+use crate::traits::{GrayImageView, Region};
 
 use super::Detection;
 
-use tree::DetectorTree;
+pub use constant::StaticDetector;
+pub use flat::DetectorRef;
+
+/// Maximum number of values preallocated while loading a model.
+const RESERVE_LIMIT: usize = 1 << 16;
 
 /// Implements object detection using a cascade of decision tree classifiers.
+///
+/// Model parameters are stored flat: comparison nodes, leaf predictions and
+/// tree thresholds of all trees are kept in three contiguous arrays, see
+/// [`DetectorRef`] for the borrowed counterpart.
 #[derive(Clone)]
 pub struct Detector {
     depth: usize,
-    dsize: usize,
     threshold: f32,
-    forest: Vec<DetectorTree>,
+    border: BorderMode,
+    nodes: Vec<[u8; 4]>,
+    predictions: Vec<[u8; 4]>,
+    thresholds: Vec<[u8; 4]>,
 }
 
 impl Debug for Detector {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct(stringify!(Detector))
             .field("depth", &self.depth)
-            .field("dsize", &self.dsize)
             .field("threshold", &self.threshold)
-            .field("trees", &self.forest.len())
+            .field("border", &self.border)
+            .field("trees", &self.thresholds.len())
             .finish()
     }
 }
 
 impl Detector {
+    /// Returns a borrowed view of the detector model.
+    #[inline]
+    pub fn view(&self) -> DetectorRef<'_> {
+        DetectorRef {
+            depth: self.depth,
+            threshold: self.threshold,
+            border: self.border,
+            nodes: &self.nodes,
+            predictions: &self.predictions,
+            thresholds: &self.thresholds,
+        }
+    }
+
+    /// Returns the handling of samples outside of the image.
+    #[inline]
+    pub fn border(&self) -> BorderMode {
+        self.border
+    }
+
+    /// Set the handling of samples outside of the image.
+    ///
+    /// With [`BorderMode::Reject`] windows crossing the image border are not detected.
+    #[inline]
+    pub fn set_border(&mut self, value: BorderMode) {
+        self.border = value;
+    }
+
     /// Estimate detection score for the rectangular region.
     ///
     /// ### Arguments
     ///
     /// * `image` -- target image;
-    /// * `region` -- rectangular region to classify.
+    /// * `region` -- rectangular region to classify, node offsets are scaled
+    ///   by its width and height independently, so the region may be not square.
     ///
     /// ### Returns
     ///
     /// * `Some(f32)` passed region is an object with score;
     /// * `None` -- if passed region is not an object.
     #[inline]
-    pub fn classify<I>(&self, image: &I, region: Square) -> Option<f32>
+    pub fn classify<I, R>(&self, image: &I, region: R) -> Option<f32>
     where
-        I: ExtendedImageView<Pixel = Luma<u8>>,
+        I: GrayImageView,
+        R: Region,
     {
-        let mut result = 0.0f32;
-        let point = region.center();
-
-        for tree in self.forest.iter() {
-            let idx = (0..self.depth).fold(1, |idx, _| {
-                2 * idx + !tree.nodes[idx].bintest(image, point, region.size()) as usize
-            });
-            let lutidx = idx - self.dsize;
-            result += tree.predictions[lutidx];
-
-            if result < tree.threshold {
-                return None;
-            }
-        }
-        Some(result - self.threshold)
+        self.view().classify(image, region)
+    }
+
+    /// Estimate detection scores for many regions at once.
+    ///
+    /// See [`DetectorRef::classify_batch`].
+    #[inline]
+    pub fn classify_batch<I>(&self, image: &I, regions: &[Square], scores: &mut [Option<f32>])
+    where
+        I: GrayImageView,
+    {
+        self.view().classify_batch(image, regions, scores)
     }
 
     /// Detect an object in the rectangular region.
     #[inline]
-    pub fn detect<I>(&self, image: &I, region: Square) -> Option<Detection<Square>>
+    pub fn detect<I, R>(&self, image: &I, region: R) -> Option<Detection<R>>
     where
-        I: ExtendedImageView<Pixel = Luma<u8>>,
+        I: GrayImageView,
+        R: Region,
     {
-        self.classify(image, region)
-            .map(|score| Detection { region, score })
+        self.view().detect(image, region)
     }
 
     /// Create a detector object from a readable source.
@@ -95,29 +135,145 @@ impl Detector {
         readable.read_exact(&mut buffer)?;
         let ntrees = i32::from_le_bytes(buffer) as usize;
 
-        let mut trees: Vec<DetectorTree> = Vec::with_capacity(ntrees);
+        let overflow = || Error::other("depth overflow");
+        let nnodes = ntrees.checked_mul(tree_size - 1).ok_or_else(overflow)?;
+        let npredictions = ntrees.checked_mul(tree_size).ok_or_else(overflow)?;
+
+        // sizes come from the header, so the input may be shorter than declared
+        let mut nodes = Vec::with_capacity(nnodes.min(RESERVE_LIMIT));
+        let mut predictions = Vec::with_capacity(npredictions.min(RESERVE_LIMIT));
+        let mut thresholds = Vec::with_capacity(ntrees.min(RESERVE_LIMIT));
 
         for _ in 0..ntrees {
-            trees.push(DetectorTree::load(&mut readable, tree_size)?);
+            for _ in 1..tree_size {
+                readable.read_exact(&mut buffer)?;
+                nodes.push(buffer);
+            }
+
+            for _ in 0..tree_size {
+                readable.read_exact(&mut buffer)?;
+                predictions.push(buffer);
+            }
+
+            readable.read_exact(&mut buffer)?;
+            thresholds.push(buffer);
         }
 
-        let threshold = trees.last().ok_or(Error::other("No trees"))?.threshold;
+        let threshold = thresholds
+            .last()
+            .map(|value| f32::from_le_bytes(*value))
+            .ok_or(Error::other("No trees"))?;
 
         Ok(Self {
             depth,
-            dsize: tree_size,
-            forest: trees,
             threshold,
+            border: BorderMode::Clamp,
+            nodes,
+            predictions,
+            thresholds,
+        })
+    }
+
+    /// Write the detector model in the flat format.
+    ///
+    /// Output can be borrowed later without copying with [`DetectorRef::from_bytes`].
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn write_flat(&self, writable: impl Write) -> Result<(), Error> {
+        self.view().write_flat(writable)
+    }
+}
+
+/// Serialize trees of the same `depth` given as `(nodes, predictions, threshold)`
+/// into the original detector model format readable by [`Detector::load`].
+#[cfg(test)]
+pub(crate) fn model_bytes<T>(depth: usize, trees: T) -> Vec<u8>
+where
+    T: IntoIterator<Item = (Vec<[u8; 4]>, Vec<f32>, f32)>,
+{
+    let trees: Vec<_> = trees.into_iter().collect();
+
+    let mut data = vec![0u8; 8];
+    data.extend((depth as i32).to_le_bytes());
+    data.extend((trees.len() as i32).to_le_bytes());
+
+    for (nodes, predictions, threshold) in trees {
+        assert_eq!(nodes.len(), (1 << depth) - 1);
+        assert_eq!(predictions.len(), 1 << depth);
+
+        data.extend(nodes.into_iter().flatten());
+        data.extend(predictions.iter().flat_map(|value| value.to_le_bytes()));
+        data.extend(threshold.to_le_bytes());
+    }
+
+    data
+}
+
+/// Detector of random trees of the same `depth`, thresholds
+/// grow along the cascade, so a part of windows is rejected.
+#[cfg(all(test, feature = "std"))]
+pub(crate) fn random_detector(rng: &mut impl rand::Rng, depth: usize, ntrees: usize) -> Detector {
+    let trees: Vec<_> = (0..ntrees)
+        .map(|i| {
+            (
+                (1..(1 << depth)).map(|_| rng.random()).collect(),
+                (0..(1 << depth))
+                    .map(|_| rng.random_range(-1.0..1.0))
+                    .collect(),
+                -2.0 + 0.2 * i as f32,
+            )
         })
+        .collect();
+
+    Detector::load(model_bytes(depth, trees).as_slice()).unwrap()
+}
+
+impl<'a> From<&'a Detector> for DetectorRef<'a> {
+    #[inline]
+    fn from(detector: &'a Detector) -> Self {
+        detector.view()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::io::ErrorKind;
     use crate::nodes::ComparisonNode;
 
     use super::*;
 
+    #[test]
+    fn test_detector_load_slice() {
+        let data = model_bytes(
+            1,
+            [-1.0, 0.5].map(|threshold| (vec![[0, 129, 0, 127]], vec![1.0, -1.0], threshold)),
+        );
+
+        let detector = Detector::load(data.as_slice()).unwrap();
+        assert_eq!(detector.thresholds.len(), 2);
+        assert_abs_diff_eq!(detector.threshold, 0.5);
+
+        let error = Detector::load(&data[..data.len() - 1]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_detector_load_truncated_huge_header() {
+        let mut data = vec![0u8; 8];
+        data.extend(20i32.to_le_bytes());
+        data.extend(5000i32.to_le_bytes());
+        data.resize(80, 0);
+
+        let error = Detector::load(data.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+
+        data[12..16].copy_from_slice(&i32::MAX.to_le_bytes());
+        data[8..12].copy_from_slice(&40i32.to_le_bytes());
+
+        let error = Detector::load(data.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Other);
+    }
+
     #[test]
     fn test_face_detector_model_loading() {
         let facefinder = dbg!(Detector::load(
@@ -127,37 +283,20 @@ mod tests {
         )
         .expect("parsing failed"));
 
-        // for tree in facefinder.forest.iter() {
-        //     println!("{:?}", tree);
-        // }
-
         assert_eq!(6, facefinder.depth);
-        assert_eq!(468, facefinder.forest.len());
+        assert_eq!(468, facefinder.thresholds.len());
+
+        let view = facefinder.view();
 
         let second_node = ComparisonNode::from([-17i8, 36i8, -55i8, 7i8]);
         let last_node = ComparisonNode::from([-26i8, -84i8, -48i8, 0i8]);
-        assert_eq!(second_node, facefinder.forest[0].nodes[1]);
-        assert_eq!(
-            last_node,
-            *facefinder.forest.last().unwrap().nodes.last().unwrap(),
-        );
+        assert_eq!(second_node, view.node(0, 1));
+        assert_eq!(last_node, view.node(467, 63));
 
-        assert_abs_diff_eq!(facefinder.forest[0].threshold, -0.7550662159919739f32);
-        assert_abs_diff_eq!(
-            facefinder.forest.last().unwrap().threshold,
-            -1.9176125526428223f32
-        );
+        assert_abs_diff_eq!(view.tree_threshold(0), -0.7550662f32);
+        assert_abs_diff_eq!(view.tree_threshold(467), -1.9176126f32);
 
-        assert_abs_diff_eq!(facefinder.forest[0].predictions[0], -0.7820115089416504f32);
-        assert_abs_diff_eq!(
-            *facefinder
-                .forest
-                .last()
-                .unwrap()
-                .predictions
-                .last()
-                .unwrap(),
-            0.07058460265398026f32
-        );
+        assert_abs_diff_eq!(view.prediction(0, 0), -0.7820115f32);
+        assert_abs_diff_eq!(view.prediction(467, 63), 0.0705846f32);
     }
 }
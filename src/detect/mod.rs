@@ -1,25 +1,43 @@
 mod detection;
 mod detector;
+#[cfg(feature = "std")]
+mod multi;
+#[cfg(feature = "std")]
 mod padding;
 
 pub mod clusterize;
+#[cfg(feature = "std")]
 pub mod multiscale;
+pub mod reference;
 
+#[cfg(feature = "std")]
 use derive_builder::Builder;
-use image::Luma;
-use pixelutil_image::ExtendedImageView;
 
-use crate::geometry::Target;
+#[cfg(feature = "std")]
+use crate::geometry::{Rectangle, Target};
+#[cfg(feature = "std")]
+use crate::traits::GrayImageView;
 
+#[cfg(feature = "std")]
 use clusterize::Clusterizer;
-use multiscale::Multiscaler;
+#[cfg(feature = "std")]
+use multiscale::{Multiscaler, MultiscalerError};
 
 pub use detection::Detection;
-pub use detector::Detector;
+pub use detector::{Detector, DetectorRef, StaticDetector};
+
+#[cfg(test)]
+pub(crate) use detector::model_bytes;
+#[cfg(all(test, feature = "std"))]
+pub(crate) use detector::random_detector;
+#[cfg(feature = "std")]
+pub use multi::MultiDetector;
+#[cfg(feature = "std")]
 pub use padding::Padding;
 
 /// Utility for running multiscale detection with clustering and padding
-/// using [`Detector`].
+/// using [`Detector`] or borrowed [`DetectorRef`].
+#[cfg(feature = "std")]
 #[derive(Debug, Clone, Copy, Builder)]
 #[builder]
 pub struct DetectMultiscale {
@@ -33,6 +51,7 @@ pub struct DetectMultiscale {
     pub padding: Padding,
 }
 
+#[cfg(feature = "std")]
 impl DetectMultiscale {
     /// Create default builder struct.
     #[inline]
@@ -42,18 +61,32 @@ impl DetectMultiscale {
 
     /// Run multiscale detection with clustering and padding on the specified image.
     #[inline]
-    pub fn run<I>(&self, detector: &Detector, image: &I) -> Vec<Detection<Target>>
+    pub fn run<'a, D, I>(&self, detector: D, image: &I) -> Vec<Detection<Target>>
     where
-        I: ExtendedImageView<Pixel = Luma<u8>>,
+        D: Into<DetectorRef<'a>>,
+        I: GrayImageView,
     {
+        let detector = detector.into();
         let mut detections = Vec::new();
 
-        self.multiscaler
-            .run(self.padding.image_rect(image), |region| {
-                if let Some(detection) = detector.detect(image, region) {
-                    detections.push(detection);
+        let (width, height) = image.size();
+
+        self.multiscaler.run_interior(
+            self.padding.rect(width, height),
+            (width, height),
+            |region, interior| {
+                let score = if interior {
+                    // SAFETY: all node offsets of the region are inside the image.
+                    unsafe { detector.run_interior(detector.depth, image, &region) }
+                } else {
+                    detector.run_border(detector.depth, image, &region)
+                };
+
+                if let Some(score) = score {
+                    detections.push(Detection { region, score });
                 }
-            });
+            },
+        );
 
         let mut clusters = Vec::new();
 
@@ -61,4 +94,183 @@ impl DetectMultiscale {
 
         clusters
     }
+
+    /// Run multiscale detection of rectangular regions with clustering and padding,
+    /// e.g. for pedestrians or license plates.
+    ///
+    /// Regions are generated by [`Multiscaler::run_rect`] with its aspect ratio
+    /// and clustered keeping their width and height.
+    #[inline]
+    pub fn run_rect<'a, D, I>(&self, detector: D, image: &I) -> Vec<Detection<Rectangle>>
+    where
+        D: Into<DetectorRef<'a>>,
+        I: GrayImageView,
+    {
+        let detector = detector.into();
+        let mut detections = Vec::new();
+
+        let (width, height) = image.size();
+
+        self.multiscaler.run_rect_interior(
+            self.padding.rect(width, height),
+            (width, height),
+            |region, interior| {
+                let score = if interior {
+                    // SAFETY: all node offsets of the region are inside the image.
+                    unsafe { detector.run_interior(detector.depth, image, &region) }
+                } else {
+                    detector.run_border(detector.depth, image, &region)
+                };
+
+                if let Some(score) = score {
+                    detections.push(Detection { region, score });
+                }
+            },
+        );
+
+        let mut clusters = Vec::new();
+
+        self.clusterizer
+            .clusterize_rect(&mut detections, &mut clusters);
+
+        clusters
+    }
+
+    /// Run multiscale detection of several detectors with class labels
+    /// scanning the image once, with clustering per class and padding.
+    ///
+    /// Each detector classifies only regions within both its own size range
+    /// and the range of the multiscaler.
+    ///
+    /// Returns [`DetectorSizeNotScanned`](MultiscalerError::DetectorSizeNotScanned)
+    /// with the index of the first detector whose size range contains none
+    /// of the [`sizes`](Multiscaler::sizes) scanned by the multiscaler.
+    #[inline]
+    pub fn run_multi<L, I>(
+        &self,
+        detector: &MultiDetector<L>,
+        image: &I,
+    ) -> Result<Vec<(L, Detection<Target>)>, MultiscalerError>
+    where
+        L: Clone,
+        I: GrayImageView,
+    {
+        if let Some(index) = detector
+            .models
+            .iter()
+            .position(|model| !self.multiscaler.sizes().any(|size| model.contains(size)))
+        {
+            return Err(MultiscalerError::DetectorSizeNotScanned(index));
+        }
+
+        let models: Vec<_> = detector
+            .models
+            .iter()
+            .map(|model| (model, model.detector.view()))
+            .collect();
+        let mut detections = vec![Vec::new(); models.len()];
+
+        let (width, height) = image.size();
+
+        self.multiscaler.run_interior(
+            self.padding.rect(width, height),
+            (width, height),
+            |region, interior| {
+                for ((model, view), detections) in models.iter().zip(detections.iter_mut()) {
+                    if !model.contains(region.size()) {
+                        continue;
+                    }
+
+                    let score = if interior {
+                        // SAFETY: all node offsets of the region are inside the image.
+                        unsafe { view.run_interior(view.depth, image, &region) }
+                    } else {
+                        view.run_border(view.depth, image, &region)
+                    };
+
+                    if let Some(score) = score {
+                        detections.push(Detection { region, score });
+                    }
+                }
+            },
+        );
+
+        let mut result = Vec::new();
+        let mut clusters = Vec::new();
+
+        for ((model, _), detections) in models.iter().zip(detections.iter_mut()) {
+            clusters.clear();
+            self.clusterizer.clusterize(detections, &mut clusters);
+
+            result.extend(
+                clusters
+                    .iter()
+                    .map(|detection| (model.label.clone(), *detection)),
+            );
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use image::{GrayImage, Luma};
+
+    use crate::traits::Region;
+
+    use super::*;
+
+    #[test]
+    fn test_detect_multiscale_run_rect() {
+        // bright vertical bar on the dark background
+        let image = GrayImage::from_fn(48, 48, |x, _| {
+            Luma([if (16..24).contains(&x) { 200 } else { 0 }])
+        });
+
+        // center of the window is brighter than its left edge
+        let data = model_bytes(1, [(vec![[0, 0, 0, 128]], vec![1.0, -1.0], 0.0)]);
+        let detector = Detector::load(data.as_slice()).unwrap();
+
+        let detect = DetectMultiscale::builder()
+            .multiscaler(
+                Multiscaler::new(16, 32, 0.25, 2.0)
+                    .unwrap()
+                    .with_aspect_ratio(0.5)
+                    .unwrap(),
+            )
+            .padding(Padding::new(-4, -4, -4, -4))
+            .build()
+            .unwrap();
+
+        let detections = detect.run_rect(&detector, &image);
+        assert!(!detections.is_empty());
+
+        // same as classifying each region and clustering them
+        let mut expected = Vec::new();
+        detect
+            .multiscaler
+            .run_rect(detect.padding.rect(48, 48), |region| {
+                if let Some(detection) = detector.detect(&image, region) {
+                    expected.push(detection);
+                }
+            });
+        assert!(expected.iter().any(|d| d.region().top() < 0));
+
+        let mut clusters = Vec::new();
+        detect
+            .clusterizer
+            .clusterize_rect(&mut expected, &mut clusters);
+
+        assert_eq!(detections.len(), clusters.len());
+
+        for (detection, cluster) in detections.iter().zip(clusters.iter()) {
+            assert_eq!(detection.region(), cluster.region());
+            assert_abs_diff_eq!(detection.score(), cluster.score());
+
+            let region = detection.region();
+            assert_eq!(region.width() * 2, region.height());
+            assert!(region.left() < 24 && region.right() > 16);
+        }
+    }
 }
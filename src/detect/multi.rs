@@ -0,0 +1,203 @@
+use super::multiscale::MultiscalerError;
+use super::Detector;
+
+/// Detector model with its class label and range of region sizes.
+#[derive(Debug, Clone)]
+pub(super) struct LabeledDetector<L> {
+    pub(super) label: L,
+    pub(super) detector: Detector,
+    pub(super) min_size: u32,
+    pub(super) max_size: u32,
+}
+
+impl<L> LabeledDetector<L> {
+    /// Whether regions of the size are classified by the detector.
+    #[inline]
+    pub(super) fn contains(&self, size: u32) -> bool {
+        (self.min_size..=self.max_size).contains(&size)
+    }
+}
+
+/// Set of detectors with class labels run in one multiscale pass,
+/// see [`DetectMultiscale::run_multi`](super::DetectMultiscale::run_multi).
+///
+/// Each region generated by the multiscaler is classified by every
+/// detector whose size range contains the region size, so the range
+/// should contain at least one of the [`sizes`](super::multiscale::Multiscaler::sizes)
+/// of the multiscaler.
+#[derive(Debug, Clone)]
+pub struct MultiDetector<L> {
+    pub(super) models: Vec<LabeledDetector<L>>,
+}
+
+impl<L> MultiDetector<L> {
+    /// Create an empty set of detectors.
+    #[inline]
+    pub fn new() -> Self {
+        Self { models: Vec::new() }
+    }
+
+    /// Add the detector with the class label.
+    ///
+    /// ### Arguments
+    ///
+    /// * `label` -- class label of detections;
+    /// * `detector` -- detector model;
+    /// * `min_size` -- minimum size of the square region to detect;
+    /// * `max_size` -- maximum size of the square region to detect.
+    #[inline]
+    pub fn with_detector(
+        mut self,
+        label: L,
+        detector: Detector,
+        min_size: u32,
+        max_size: u32,
+    ) -> Result<Self, MultiscalerError> {
+        if min_size == 0 {
+            return Err(MultiscalerError::MinSizeIsZero);
+        }
+
+        if min_size > max_size {
+            return Err(MultiscalerError::MaxSizeLessThanMinSize);
+        }
+
+        self.models.push(LabeledDetector {
+            label,
+            detector,
+            min_size,
+            max_size,
+        });
+
+        Ok(self)
+    }
+
+    /// Returns the number of detectors.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.models.len()
+    }
+
+    /// Returns `true` if there are no detectors.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.models.is_empty()
+    }
+
+    /// Returns class labels in the order of detectors.
+    #[inline]
+    pub fn labels(&self) -> impl Iterator<Item = &L> {
+        self.models.iter().map(|model| &model.label)
+    }
+}
+
+impl<L> Default for MultiDetector<L> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{GrayImage, Luma};
+
+    use crate::detect::clusterize::Clusterizer;
+    use crate::detect::model_bytes;
+    use crate::detect::multiscale::Multiscaler;
+    use crate::detect::{DetectMultiscale, Padding};
+    use crate::traits::Region;
+
+    use super::*;
+
+    /// Single stump comparing pixels left and right of the center,
+    /// detecting windows centered on a bright to dark vertical edge.
+    fn detector(bright: bool) -> Detector {
+        let node = if bright {
+            [0, 128, 0, 127]
+        } else {
+            [0, 127, 0, 128]
+        };
+
+        let data = model_bytes(1, [(vec![node], vec![1.0, -1.0], 0.0)]);
+        Detector::load(data.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_multi_detector_run() {
+        let image = GrayImage::from_fn(32, 32, |x, _| Luma([if x < 16 { 200 } else { 0 }]));
+
+        let multi = MultiDetector::new()
+            .with_detector("edge", detector(true), 8, 8)
+            .unwrap()
+            .with_detector("large", detector(true), 16, 16)
+            .unwrap()
+            .with_detector("none", detector(false), 8, 16)
+            .unwrap();
+        assert_eq!(multi.len(), 3);
+        assert!(MultiDetector::<()>::new()
+            .with_detector((), detector(true), 9, 8)
+            .is_err());
+
+        let detect = DetectMultiscale::builder()
+            .multiscaler(Multiscaler::new(8, 16, 0.25, 2.0).unwrap())
+            .clusterizer(Clusterizer::default().intersection_threshold(0.1))
+            .padding(Padding::default())
+            .build()
+            .unwrap();
+
+        let detections = detect.run_multi(&multi, &image).unwrap();
+
+        assert!(detections.iter().all(|(label, _)| *label != "none"));
+
+        for (label, size) in [("edge", 8), ("large", 16)] {
+            let targets: Vec<_> = detections
+                .iter()
+                .filter(|(l, _)| *l == label)
+                .map(|(_, detection)| *detection)
+                .collect();
+
+            assert!(!targets.is_empty());
+
+            for target in targets.iter() {
+                assert!(target.region().left() < 16 && target.region().right() > 16);
+            }
+
+            // same as the single detector run on the size range of the model
+            let single = DetectMultiscale {
+                multiscaler: Multiscaler::new(size, size, 0.25, 2.0).unwrap(),
+                ..detect
+            }
+            .run(&detector(true), &image);
+
+            assert_eq!(targets.len(), single.len());
+
+            for (target, expected) in targets.iter().zip(single.iter()) {
+                assert_eq!(target.region(), expected.region());
+                assert_abs_diff_eq!(target.score(), expected.score());
+            }
+        }
+    }
+
+    #[test]
+    fn test_multi_detector_sizes_not_scanned() {
+        let image = GrayImage::new(32, 32);
+        let detect = DetectMultiscale::builder()
+            .multiscaler(Multiscaler::new(8, 16, 0.25, 2.0).unwrap())
+            .build()
+            .unwrap();
+
+        // sizes 8 and 16 are scanned only
+        for (min_size, max_size) in [(32, 64), (1, 7), (9, 15)] {
+            let multi = MultiDetector::new()
+                .with_detector("edge", detector(true), 8, 16)
+                .unwrap()
+                .with_detector("missed", detector(true), min_size, max_size)
+                .unwrap();
+
+            assert!(matches!(
+                detect.run_multi(&multi, &image),
+                Err(MultiscalerError::DetectorSizeNotScanned(1))
+            ));
+        }
+    }
+}
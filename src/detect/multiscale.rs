@@ -1,7 +1,9 @@
 use imageproc::rect::Rect;
 use thiserror::Error;
 
-use crate::geometry::Square;
+use crate::geometry::{Rectangle, Square};
+use crate::nodes::ComparisonNode;
+use crate::traits::Region;
 
 /// Multiscale object detection parameters.
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -10,9 +12,11 @@ pub struct Multiscaler {
     max_size: u32,
     shift_factor: f32,
     scale_factor: f32,
+    aspect_ratio: f32,
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum MultiscalerError {
     #[error("`min_size` should be non zero")]
     MinSizeIsZero,
@@ -20,8 +24,12 @@ pub enum MultiscalerError {
     MaxSizeLessThanMinSize,
     #[error("`shift_factor` should be in `(0, 1]` range")]
     ShiftFactorOutOfRange,
-    #[error("`scale_factor` should be greater than 1")]
+    #[error("`scale_factor` should be finite and greater than 1")]
     ScaleFactorLessThanOne,
+    #[error("`aspect_ratio` should be positive and finite")]
+    AspectRatioNotPositive,
+    #[error("detector `{0}` size range contains none of the scanned sizes")]
+    DetectorSizeNotScanned(usize),
 }
 
 impl Multiscaler {
@@ -48,11 +56,12 @@ impl Multiscaler {
             return Err(MultiscalerError::MaxSizeLessThanMinSize);
         }
 
-        if !(0.0..=1.0).contains(&shift_factor) {
+        // negated comparisons reject NaN, window sizes and positions never change otherwise
+        if !(shift_factor > 0.0 && shift_factor <= 1.0) {
             return Err(MultiscalerError::ShiftFactorOutOfRange);
         }
 
-        if scale_factor < 1.0 {
+        if !(scale_factor > 1.0 && scale_factor.is_finite()) {
             return Err(MultiscalerError::ScaleFactorLessThanOne);
         }
 
@@ -61,6 +70,21 @@ impl Multiscaler {
             max_size,
             shift_factor,
             scale_factor,
+            aspect_ratio: 1.0,
+        })
+    }
+
+    /// Set the ratio of width to height of rectangular regions
+    /// generated by [`run_rect`](Self::run_rect), `1` by default.
+    #[inline]
+    pub fn with_aspect_ratio(self, value: f32) -> Result<Self, MultiscalerError> {
+        if !value.is_finite() || value <= 0.0 {
+            return Err(MultiscalerError::AspectRatioNotPositive);
+        }
+
+        Ok(Self {
+            aspect_ratio: value,
+            ..self
         })
     }
 
@@ -84,6 +108,21 @@ impl Multiscaler {
         self.scale_factor
     }
 
+    /// Returns the ratio of width to height of rectangular regions.
+    pub fn aspect_ratio(&self) -> f32 {
+        self.aspect_ratio
+    }
+
+    /// Returns sizes of regions in the order they are scanned.
+    pub fn sizes(&self) -> impl Iterator<Item = u32> {
+        let (max_size, scale_factor) = (self.max_size, self.scale_factor);
+
+        core::iter::successors(Some(self.min_size), move |&size| {
+            Some((size as f32 * scale_factor) as u32)
+        })
+        .take_while(move |&size| size <= max_size)
+    }
+
     /// Run multiscale detection on the specified rectangle.
     ///
     /// ### Arguments
@@ -105,6 +144,77 @@ impl Multiscaler {
         )
     }
 
+    /// Run multiscale detection of rectangular regions on the specified rectangle.
+    ///
+    /// Sizes define the height of regions and their width is scaled
+    /// by [`aspect_ratio`](Self::aspect_ratio), regions are shifted
+    /// by the corresponding fraction of their width and height.
+    ///
+    /// ### Arguments
+    ///
+    /// * `rect` -- rectangle to run detection on;
+    /// * `f` -- function to call for each generated rectangular region.
+    #[inline]
+    pub fn run_rect<F>(&self, rect: Rect, f: F)
+    where
+        F: FnMut(Rectangle),
+    {
+        multiscale_rect(
+            self.min_size,
+            self.max_size,
+            self.shift_factor,
+            self.scale_factor,
+            self.aspect_ratio,
+            rect,
+            f,
+        )
+    }
+
+    /// Run multiscale detection on the specified rectangle of the image
+    /// telling whether each region is sampled inside the image.
+    ///
+    /// ### Arguments
+    ///
+    /// * `rect` -- rectangle to run detection on;
+    /// * `dimensions` -- `(width, height)` of the image;
+    /// * `f` -- function to call for each generated square region and `true`
+    ///   if all its samples are inside the image, so no clamping is needed.
+    #[inline]
+    pub fn run_interior<F>(&self, rect: Rect, dimensions: (u32, u32), mut f: F)
+    where
+        F: FnMut(Square, bool),
+    {
+        self.run(rect, |region| {
+            f(
+                region,
+                ComparisonNode::interior(
+                    dimensions,
+                    region.center(),
+                    (region.size(), region.size()),
+                ),
+            )
+        })
+    }
+
+    /// Same as [`run_interior`](Self::run_interior) for rectangular regions
+    /// generated by [`run_rect`](Self::run_rect).
+    #[inline]
+    pub fn run_rect_interior<F>(&self, rect: Rect, dimensions: (u32, u32), mut f: F)
+    where
+        F: FnMut(Rectangle, bool),
+    {
+        self.run_rect(rect, |region| {
+            f(
+                region,
+                ComparisonNode::interior(
+                    dimensions,
+                    region.center(),
+                    (region.width(), region.height()),
+                ),
+            )
+        })
+    }
+
     /// Count the number of square regions that would be generated
     /// by running multiscale detection on the specified rectangle.
     #[inline]
@@ -169,6 +279,56 @@ pub fn multiscale<F>(
     }
 }
 
+/// Run multiscale detection of rectangular regions on the specified rectangle.
+///
+/// ### Arguments
+///
+/// * `min_size` -- minimum height of the region to detect;
+/// * `max_size` -- maximum height of the region to detect;
+/// * `shift_factor` -- factor to shift the detection window;
+/// * `scale_factor` -- factor to scale the detection window;
+/// * `aspect_ratio` -- ratio of width to height of the detection window;
+/// * `rect` -- boundary rectangle to run detection on;
+/// * `f` -- function to call for each generated rectangular region.
+#[inline]
+pub fn multiscale_rect<F>(
+    min_size: u32,
+    max_size: u32,
+    shift_factor: f32,
+    scale_factor: f32,
+    aspect_ratio: f32,
+    rect: Rect,
+    mut f: F,
+) where
+    F: FnMut(Rectangle),
+{
+    let mut height = min_size;
+
+    let start_x = rect.left();
+    let start_y = rect.top();
+
+    let right = start_x + rect.width() as i32;
+    let bottom = start_y + rect.height() as i32;
+
+    while height <= max_size {
+        let heightf = height as f32;
+        let width = 1.max((heightf * aspect_ratio) as u32);
+
+        let step_x: usize = 1.max((width as f32 * shift_factor) as usize);
+        let step_y: usize = 1.max((heightf * shift_factor) as usize);
+
+        let end_x = right - width as i32;
+        let end_y = bottom - height as i32;
+
+        for y in (start_y..=end_y).step_by(step_y) {
+            for x in (start_x..=end_x).step_by(step_x) {
+                f(Rectangle::new(x, y, width, height))
+            }
+        }
+        height = (heightf * scale_factor) as u32;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -178,4 +338,103 @@ mod tests {
         let ms = Multiscaler::new(1, 4, 1.0, 2.0).unwrap();
         ms.run(Rect::at(0, 0).of_size(4, 4), |s| println!("{:?}", s));
     }
+
+    #[test]
+    fn test_multiscale_sizes() {
+        let ms = Multiscaler::new(10, 30, 0.5, 1.5).unwrap();
+        assert_eq!(ms.sizes().collect::<Vec<_>>(), vec![10, 15, 22]);
+
+        let mut sizes = Vec::new();
+        ms.run(Rect::at(0, 0).of_size(40, 40), |s| {
+            if sizes.last() != Some(&s.size()) {
+                sizes.push(s.size());
+            }
+        });
+        assert_eq!(ms.sizes().collect::<Vec<_>>(), sizes);
+    }
+
+    #[test]
+    fn test_multiscale_invalid_factors() {
+        for shift_factor in [0.0, -0.5, 1.5, f32::NAN] {
+            assert!(matches!(
+                Multiscaler::new(10, 20, shift_factor, 1.1),
+                Err(MultiscalerError::ShiftFactorOutOfRange)
+            ));
+        }
+
+        for scale_factor in [1.0, 0.5, f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                Multiscaler::new(10, 20, 0.1, scale_factor),
+                Err(MultiscalerError::ScaleFactorLessThanOne)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_multiscale_run_rect() {
+        let ms = Multiscaler::new(10, 20, 0.5, 2.0)
+            .unwrap()
+            .with_aspect_ratio(0.5)
+            .unwrap();
+        let rect = Rect::at(0, 0).of_size(40, 30);
+
+        let mut regions = Vec::new();
+        ms.run_rect(rect, |r| regions.push(r));
+
+        // 5 rows of 18 windows of 5x10 and 2 rows of 7 windows of 10x20
+        assert_eq!(regions.len(), 5 * 18 + 2 * 7);
+        assert_eq!(regions[0], Rectangle::new(0, 0, 5, 10));
+        assert_eq!(regions[1], Rectangle::new(2, 0, 5, 10));
+        assert_eq!(regions[18], Rectangle::new(0, 5, 5, 10));
+        assert_eq!(regions[90], Rectangle::new(0, 0, 10, 20));
+
+        for region in regions {
+            assert!(region.right() < 40 && region.bottom() < 30);
+        }
+
+        // square regions are the same as of `run`
+        let ms = Multiscaler::new(4, 16, 0.25, 1.5).unwrap();
+        let mut squares = Vec::new();
+        ms.run(rect, |s| squares.push(Rectangle::from(s)));
+
+        let mut regions = Vec::new();
+        ms.run_rect(rect, |r| regions.push(r));
+        assert_eq!(regions, squares);
+
+        for value in [0.0, -1.0, f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            assert!(matches!(
+                ms.with_aspect_ratio(value),
+                Err(MultiscalerError::AspectRatioNotPositive)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_multiscale_run_interior() {
+        let ms = Multiscaler::new(20, 100, 0.1, 1.1).unwrap();
+        let (width, height) = (160, 120);
+        let rect = Rect::at(-20, -20).of_size(200, 160);
+
+        let mut interior = 0;
+        let mut border = 0;
+
+        ms.run_interior(rect, (width, height), |s, inside| {
+            // samples are at most half of the window size away from its center
+            let (center, half) = (s.center(), s.size() as i32 / 2);
+            let fits = center.x - half >= 0
+                && center.y - half >= 0
+                && center.x + half <= width as i32
+                && center.y + half <= height as i32;
+
+            if inside {
+                interior += 1;
+                assert!(fits);
+            } else {
+                border += 1;
+            }
+        });
+
+        assert!(interior > 0 && border > 0);
+        assert_eq!(interior + border, ms.count(rect));
+    }
 }
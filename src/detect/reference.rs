@@ -0,0 +1,355 @@
+//! Conformance mode following object detection of the original
+//! [pico](https://github.com/nenadmarkus/pico) C implementation.
+//!
+//! Regular detection with [`DetectMultiscale`](crate::DetectMultiscale) differs from pico in:
+//!
+//! * windows are placed by the top-left corner with integer sizes instead of
+//!   the center with fractional sizes and steps;
+//! * pixel offsets are rounded down instead of truncated towards zero and
+//!   pixels outside the image are clamped instead of rejecting the window;
+//! * the cascade rejects the window if the score is less than the threshold instead of less or equal;
+//! * detections are clustered greedily by score and intersection over union instead of
+//!   connected components of overlapping windows.
+//!
+//! Functions of this module follow `run_cascade`, `find_objects` and `cluster_detections`
+//! of `picornt.c` including the order of floating point operations. Results are tested
+//! bit-for-bit against pico output recorded with `tests/reference/record.c`.
+//! Detections are returned with the window center as the target point,
+//! so `x` is the column and `y` is the row of pico output.
+
+use alloc::{vec, vec::Vec};
+
+use nalgebra::Point2;
+
+use crate::geometry::Target;
+use crate::traits::GrayImageView;
+
+use super::{Detection, DetectorRef};
+
+/// Overlap of windows to join them into a cluster, hardcoded in pico.
+const OVERLAP_THRESHOLD: f32 = 0.3;
+
+/// Parameters of pico `find_objects`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReferenceDetect {
+    /// Minimum window size.
+    pub min_size: f32,
+    /// Maximum window size.
+    pub max_size: f32,
+    /// Factor to scale the window size, should be greater than `1`.
+    pub scale_factor: f32,
+    /// Factor of the window size to step between windows.
+    pub stride_factor: f32,
+}
+
+impl Default for ReferenceDetect {
+    /// Create parameters used by pico sample application: minimum size of `128`,
+    /// maximum size of `1024`, scale factor of `1.1` and stride factor of `0.1`.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            min_size: 128.0,
+            max_size: 1024.0,
+            scale_factor: 1.1,
+            stride_factor: 0.1,
+        }
+    }
+}
+
+impl ReferenceDetect {
+    /// Find objects on the image and cluster them,
+    /// same as pico `find_objects` followed by `cluster_detections`.
+    #[inline]
+    pub fn run<'a, D, I>(&self, detector: D, image: &I) -> Vec<Detection<Target>>
+    where
+        D: Into<DetectorRef<'a>>,
+        I: GrayImageView,
+    {
+        clusterize(&self.find(detector, image))
+    }
+
+    /// Find objects on the image without clustering, same as pico `find_objects`.
+    pub fn find<'a, D, I>(&self, detector: D, image: &I) -> Vec<Detection<Target>>
+    where
+        D: Into<DetectorRef<'a>>,
+        I: GrayImageView,
+    {
+        let detector = detector.into();
+        let (ncols, nrows) = image.size();
+        let (nrows, ncols) = (nrows as f32, ncols as f32);
+
+        let mut detections = Vec::new();
+        let mut s = self.min_size;
+
+        while s <= self.max_size {
+            let step = (self.stride_factor * s).max(1.0);
+
+            let mut r = s / 2.0 + 1.0;
+            while r <= nrows - s / 2.0 - 1.0 {
+                let mut c = s / 2.0 + 1.0;
+                while c <= ncols - s / 2.0 - 1.0 {
+                    if let Some(score) = classify(detector, image, r as i32, c as i32, s as i32) {
+                        detections.push(Detection {
+                            region: Target {
+                                point: Point2::new(c, r),
+                                size: s,
+                            },
+                            score,
+                        });
+                    }
+                    c += step;
+                }
+                r += step;
+            }
+
+            if self.scale_factor <= 1.0 {
+                break;
+            }
+
+            s *= self.scale_factor;
+        }
+
+        detections
+    }
+}
+
+/// Run the cascade on the window, same as pico `run_cascade`.
+///
+/// Returns `None` if the window is rejected or does not fit into the image.
+///
+/// ### Arguments
+///
+/// * `detector` -- cascade to run;
+/// * `image` -- image to run on;
+/// * `row` -- row of the window center;
+/// * `col` -- column of the window center;
+/// * `size` -- window size.
+pub fn classify<'a, D, I>(detector: D, image: &I, row: i32, col: i32, size: i32) -> Option<f32>
+where
+    D: Into<DetectorRef<'a>>,
+    I: GrayImageView,
+{
+    let detector = detector.into();
+    let (ncols, nrows) = image.size();
+    let (r, c, s) = (row * 256, col * 256, size);
+
+    if (r + 128 * s) / 256 >= nrows as i32
+        || (r - 128 * s) / 256 < 0
+        || (c + 128 * s) / 256 >= ncols as i32
+        || (c - 128 * s) / 256 < 0
+    {
+        return None;
+    }
+
+    // offsets are truncated towards zero and stay inside the image after the check above
+    let luma = |y: i8, x: i8| {
+        image.luma(
+            ((c + x as i32 * s) / 256) as u32,
+            ((r + y as i32 * s) / 256) as u32,
+        )
+    };
+
+    let tree_size = 1 << detector.depth;
+    let mut score = 0.0f32;
+
+    for (tree, threshold) in detector.thresholds.iter().enumerate() {
+        let nodes = &detector.nodes[tree * (tree_size - 1)..];
+        let idx = (0..detector.depth).fold(1, |idx, _| {
+            let [y0, x0, y1, x1] = nodes[idx - 1].map(|value| value as i8);
+            2 * idx + (luma(y0, x0) <= luma(y1, x1)) as usize
+        });
+
+        score += f32::from_le_bytes(detector.predictions[tree * tree_size + idx - tree_size]);
+
+        if score <= f32::from_le_bytes(*threshold) {
+            return None;
+        }
+    }
+
+    Some(score - detector.threshold)
+}
+
+/// Cluster detections into connected components of overlapping windows,
+/// same as pico `cluster_detections`.
+///
+/// Each cluster has average position and size of its windows and the sum of their scores.
+pub fn clusterize(detections: &[Detection<Target>]) -> Vec<Detection<Target>> {
+    let mut labels = vec![0usize; detections.len()];
+    let mut count = 0;
+    let mut stack = Vec::new();
+
+    for i in 0..detections.len() {
+        if labels[i] != 0 {
+            continue;
+        }
+
+        count += 1;
+        labels[i] = count;
+        stack.push(i);
+
+        while let Some(k) = stack.pop() {
+            for (j, detection) in detections.iter().enumerate() {
+                if labels[j] == 0
+                    && overlap(&detections[k].region, &detection.region) > OVERLAP_THRESHOLD
+                {
+                    labels[j] = count;
+                    stack.push(j);
+                }
+            }
+        }
+    }
+
+    (1..=count)
+        .map(|label| {
+            let (mut q, mut r, mut c, mut s) = (0.0f32, 0.0f32, 0.0f32, 0.0f32);
+            let mut k = 0;
+
+            for (detection, _) in detections
+                .iter()
+                .zip(labels.iter())
+                .filter(|(_, value)| **value == label)
+            {
+                q += detection.score;
+                r += detection.region.point.y;
+                c += detection.region.point.x;
+                s += detection.region.size;
+                k += 1;
+            }
+
+            let k = k as f32;
+
+            Detection {
+                region: Target {
+                    point: Point2::new(c / k, r / k),
+                    size: s / k,
+                },
+                score: q,
+            }
+        })
+        .collect()
+}
+
+/// Overlap of two windows, same as pico `get_overlap`.
+#[inline]
+fn overlap(a: &Target, b: &Target) -> f32 {
+    let (r1, c1, s1) = (a.point.y, a.point.x, a.size);
+    let (r2, c2, s2) = (b.point.y, b.point.x, b.size);
+
+    let overr = ((r1 + s1 / 2.0).min(r2 + s2 / 2.0) - (r1 - s1 / 2.0).max(r2 - s2 / 2.0)).max(0.0);
+    let overc = ((c1 + s1 / 2.0).min(c2 + s2 / 2.0) - (c1 - s1 / 2.0).max(c2 - s2 / 2.0)).max(0.0);
+
+    overr * overc / (s1 * s1 + s2 * s2 - overr * overc)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::detect::model_bytes;
+    use crate::traits::Region;
+    use crate::view::GrayView;
+    use crate::{Detector, Square};
+
+    use super::*;
+
+    fn detector(node: [i8; 4], predictions: [f32; 2], threshold: f32) -> Detector {
+        let node = node.map(|value| value as u8);
+        let data = model_bytes(1, [(vec![node], predictions.to_vec(), threshold)]);
+        Detector::load(data.as_slice()).unwrap()
+    }
+
+    fn detection(row: f32, col: f32, size: f32, score: f32) -> Detection<Target> {
+        Detection {
+            region: Target::new(col, row, size),
+            score,
+        }
+    }
+
+    #[test]
+    fn test_reference_classify() {
+        let detector = detector([-128, 0, 0, 0], [-1.0, 1.0], -0.5);
+
+        let data = [0, 10, 0, 0, 20, 0, 0, 0, 0];
+        let image = GrayView::packed(&data, 3, 3).unwrap();
+
+        // row offset of `-128 * 3 / 256` is truncated to the first row
+        assert_eq!(classify(&detector, &image, 1, 1, 3), Some(1.5));
+
+        let data = [0, 20, 0, 0, 10, 0, 0, 0, 0];
+        let image = GrayView::packed(&data, 3, 3).unwrap();
+
+        assert_eq!(classify(&detector, &image, 1, 1, 3), None);
+        assert_eq!(classify(&detector, &image, 1, 1, 4), None);
+    }
+
+    #[test]
+    fn test_reference_classify_threshold() {
+        let detector = detector([0, 0, 0, 0], [0.5, 0.5], 0.5);
+
+        let data = [0u8; 16];
+        let image = GrayView::packed(&data, 4, 4).unwrap();
+
+        assert_eq!(classify(&detector, &image, 2, 2, 2), None);
+        assert_eq!(detector.classify(&image, Square::new(0, 0, 2)), Some(0.0));
+    }
+
+    #[test]
+    fn test_reference_find_windows() {
+        let detector = detector([0, 0, 0, 0], [1.0, 1.0], -1.0);
+
+        let data = [0u8; 80];
+        let image = GrayView::packed(&data, 10, 8).unwrap();
+
+        let find = ReferenceDetect {
+            min_size: 4.0,
+            max_size: 5.0,
+            scale_factor: 1.25,
+            stride_factor: 0.5,
+        };
+
+        let windows: Vec<(f32, f32, f32)> = find
+            .find(&detector, &image)
+            .iter()
+            .map(|detection| {
+                assert_eq!(detection.score, 2.0);
+                let region = detection.region;
+                (region.y(), region.x(), region.size())
+            })
+            .collect();
+
+        assert_eq!(
+            windows,
+            vec![
+                (3.0, 3.0, 4.0),
+                (3.0, 5.0, 4.0),
+                (3.0, 7.0, 4.0),
+                (5.0, 3.0, 4.0),
+                (5.0, 5.0, 4.0),
+                (5.0, 7.0, 4.0),
+                (3.5, 3.5, 5.0),
+                (3.5, 6.0, 5.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reference_clusterize() {
+        let detections = [
+            detection(10.0, 10.0, 10.0, 1.0),
+            detection(30.0, 30.0, 10.0, 4.0),
+            detection(20.0, 10.0, 10.0, 8.0),
+            detection(15.0, 10.0, 10.0, 2.0),
+        ];
+
+        // the first and the third windows do not overlap, but are connected through the last one
+        assert_eq!(overlap(&detections[0].region, &detections[2].region), 0.0);
+
+        let clusters = clusterize(&detections);
+        assert_eq!(clusters.len(), 2);
+
+        assert_eq!(clusters[0].region, Target::new(10.0, 15.0, 10.0));
+        assert_eq!(clusters[0].score, 11.0);
+        assert_eq!(clusters[0].region.top_left(), Point2::new(5, 10));
+
+        assert_eq!(clusters[1].region, Target::new(30.0, 30.0, 10.0));
+        assert_eq!(clusters[1].score, 4.0);
+    }
+}
@@ -1,7 +1,11 @@
-use std::cmp::{max, min};
+use core::cmp::{max, min};
+
+use nalgebra::Point2;
 
 use crate::traits::Region;
 
+use super::RotatedRectangle;
+
 /// Calculates the intersection over union (IoU) of two regions.
 ///
 /// Returns `None` if the regions do not overlap, otherwise returns the IoU as a `f32`.
@@ -25,9 +29,114 @@ pub fn intersection_over_union<R: Region>(r1: R, r2: R) -> Option<f32> {
     Some(inter_square as f32 / union_square as f32)
 }
 
+/// Calculates the intersection over union (IoU) of two rotated rectangles
+/// taking their orientation into account.
+///
+/// Returns `None` if the rectangles do not overlap or any of them is empty,
+/// otherwise returns the IoU as a `f32`.
+#[inline]
+pub fn oriented_intersection_over_union(
+    r1: &RotatedRectangle,
+    r2: &RotatedRectangle,
+) -> Option<f32> {
+    if !(r1.area() > 0.0 && r2.area() > 0.0) {
+        return None;
+    }
+
+    let inter_area = intersection_area(&r1.corners(), &r2.corners());
+
+    if inter_area.is_nan() || inter_area <= 0.0 {
+        return None;
+    }
+
+    Some(inter_area / (r1.area() + r2.area() - inter_area))
+}
+
+/// Maximum number of polygon vertices, the intersection of two rectangles has up to 8,
+/// rounding errors on near-degenerate inputs may produce a few more.
+const POLYGON_CAPACITY: usize = 16;
+
+/// Convex polygon with a bounded number of vertices.
+#[derive(Clone, Copy)]
+struct Polygon {
+    points: [Point2<f32>; POLYGON_CAPACITY],
+    len: usize,
+}
+
+impl Polygon {
+    /// Appends the vertex, the ones beyond the capacity are dropped.
+    #[inline]
+    fn push(&mut self, point: Point2<f32>) {
+        if let Some(slot) = self.points.get_mut(self.len) {
+            *slot = point;
+            self.len += 1;
+        }
+    }
+
+    #[inline]
+    fn points(&self) -> &[Point2<f32>] {
+        &self.points[..self.len]
+    }
+}
+
+/// Area of the intersection of two convex quadrilaterals with clockwise
+/// or counter-clockwise vertices found with Sutherland-Hodgman clipping.
+#[inline]
+fn intersection_area(subject: &[Point2<f32>; 4], clip: &[Point2<f32>; 4]) -> f32 {
+    let mut polygon = Polygon {
+        points: [Point2::origin(); POLYGON_CAPACITY],
+        len: 0,
+    };
+    subject.iter().for_each(|point| polygon.push(*point));
+
+    // orientation of the clip polygon to know which side of an edge is inside
+    let orientation = signed_area(clip).signum();
+
+    for (i, a) in clip.iter().enumerate() {
+        let b = clip[(i + 1) % clip.len()];
+        let side = |p: &Point2<f32>| orientation * (b - a).perp(&(p - a));
+
+        let input = polygon;
+        polygon.len = 0;
+
+        for (j, p) in input.points().iter().enumerate() {
+            let q = input.points()[(j + 1) % input.len];
+            let (sp, sq) = (side(p), side(&q));
+
+            if sp >= 0.0 {
+                polygon.push(*p);
+            }
+
+            if (sp >= 0.0) != (sq >= 0.0) {
+                polygon.push(p + (q - p) * (sp / (sp - sq)));
+            }
+        }
+
+        if polygon.len == 0 {
+            return 0.0;
+        }
+    }
+
+    signed_area(polygon.points()).abs()
+}
+
+/// Signed area of the polygon with the shoelace formula.
+#[inline]
+fn signed_area(points: &[Point2<f32>]) -> f32 {
+    let sum: f32 = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(p, q)| p.coords.perp(&q.coords))
+        .sum();
+
+    sum / 2.0
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::geometry::Square;
+    use alloc::vec::Vec;
+
+    use crate::geometry::{RotatedRectangle, Square};
 
     use super::*;
 
@@ -78,4 +187,77 @@ mod tests {
             };
         }
     }
+
+    #[test]
+    fn test_oriented_intersection_over_union() {
+        let r1 = RotatedRectangle::new(0.0, 0.0, 4.0, 2.0, 0.0);
+
+        assert_abs_diff_eq!(oriented_intersection_over_union(&r1, &r1).unwrap(), 1.0);
+
+        // a half of the first rectangle
+        let r2 = RotatedRectangle::new(1.0, 0.0, 2.0, 2.0, 0.0);
+        assert_abs_diff_eq!(oriented_intersection_over_union(&r1, &r2).unwrap(), 0.5);
+
+        // rotated by 90 degrees the intersection is a 2x2 square
+        let r3 = RotatedRectangle::new(0.0, 0.0, 4.0, 2.0, core::f32::consts::FRAC_PI_2);
+        let iou = oriented_intersection_over_union(&r1, &r3).unwrap();
+        assert_abs_diff_eq!(iou, 4.0 / 12.0, epsilon = 1e-5);
+        assert_abs_diff_eq!(
+            oriented_intersection_over_union(&r3, &r1).unwrap(),
+            iou,
+            epsilon = 1e-5
+        );
+
+        // square rotated by 45 degrees inside of the circumscribed square
+        let r4 = RotatedRectangle::new(0.0, 0.0, 2.0, 2.0, 0.0);
+        let r5 = RotatedRectangle::new(0.0, 0.0, 2.0, 2.0, core::f32::consts::FRAC_PI_4);
+        let inter = 8.0 * (2.0f32.sqrt() - 1.0);
+        assert_abs_diff_eq!(
+            oriented_intersection_over_union(&r4, &r5).unwrap(),
+            inter / (8.0 - inter),
+            epsilon = 1e-5
+        );
+
+        let r6 = RotatedRectangle::new(10.0, 0.0, 4.0, 2.0, 0.3);
+        assert!(oriented_intersection_over_union(&r1, &r6).is_none());
+
+        // the same as of axis-aligned regions for the ones without rotation
+        let (s1, s2) = (Square::at(0, 0).of_size(4), Square::at(2, 2).of_size(4));
+        assert_abs_diff_eq!(
+            oriented_intersection_over_union(&s1.into(), &s2.into()).unwrap(),
+            intersection_over_union(s1, s2).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_polygon_push_capacity() {
+        let mut polygon = Polygon {
+            points: [Point2::origin(); POLYGON_CAPACITY],
+            len: 0,
+        };
+
+        for i in 0..2 * POLYGON_CAPACITY {
+            polygon.push(Point2::new(i as f32, 0.0));
+        }
+
+        assert_eq!(polygon.points().len(), POLYGON_CAPACITY);
+        assert_eq!(polygon.points()[POLYGON_CAPACITY - 1].x, 15.0);
+    }
+
+    #[test]
+    fn test_oriented_intersection_over_union_degenerate() {
+        let r1 = RotatedRectangle::new(0.0, 0.0, 4.0, 2.0, 0.0);
+        let tests = [
+            RotatedRectangle::new(0.0, 0.0, 0.0, 0.0, 0.3),
+            RotatedRectangle::new(0.0, 0.0, 1e-7, 4.0, 1e-7),
+            RotatedRectangle::new(2.0, 1.0, 4.0, 2.0, f32::EPSILON),
+            RotatedRectangle::new(0.0, 0.0, f32::NAN, 2.0, 0.0),
+        ];
+
+        for r2 in tests.iter() {
+            if let Some(iou) = oriented_intersection_over_union(&r1, r2) {
+                assert!((0.0..=1.0).contains(&iou));
+            }
+        }
+    }
 }
@@ -1,7 +1,11 @@
 mod iou;
+mod rectangle;
+mod rotated;
 mod square;
 mod target;
 
-pub use iou::intersection_over_union;
+pub use iou::{intersection_over_union, oriented_intersection_over_union};
+pub use rectangle::Rectangle;
+pub use rotated::RotatedRectangle;
 pub use square::Square;
 pub use target::Target;
@@ -0,0 +1,83 @@
+#[cfg(feature = "std")]
+use imageproc::rect::Rect;
+
+use crate::traits::region::Region;
+
+use super::Square;
+
+/// Represents a rectangular region in an image with a left, top coordinates, width and height.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rectangle {
+    pub(crate) left: i32,
+    pub(crate) top: i32,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+}
+
+impl Rectangle {
+    /// Creates a new rectangle with the specified left, top coordinates, width and height.
+    #[inline]
+    pub fn new(left: i32, top: i32, width: u32, height: u32) -> Self {
+        Self {
+            left,
+            top,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a rectangle from any region.
+    #[inline]
+    pub fn from_region<T: Region>(value: T) -> Self {
+        Self::new(value.left(), value.top(), value.width(), value.height())
+    }
+}
+
+impl Region for Rectangle {
+    #[inline]
+    fn left(&self) -> i32 {
+        self.left
+    }
+
+    #[inline]
+    fn top(&self) -> i32 {
+        self.top
+    }
+
+    #[inline]
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+impl From<Square> for Rectangle {
+    #[inline]
+    fn from(value: Square) -> Self {
+        Self::new(value.left, value.top, value.size, value.size)
+    }
+}
+
+impl From<(i32, i32, u32, u32)> for Rectangle {
+    fn from(value: (i32, i32, u32, u32)) -> Self {
+        Self::new(value.0, value.1, value.2, value.3)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Rectangle> for Rect {
+    fn from(value: Rectangle) -> Self {
+        Self::at(value.left, value.top).of_size(value.width, value.height)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Rect> for Rectangle {
+    fn from(value: Rect) -> Self {
+        Self::new(value.left(), value.top(), value.width(), value.height())
+    }
+}
@@ -0,0 +1,211 @@
+use nalgebra::{ComplexField, Point2, Rotation2, Vector2};
+
+use crate::traits::Region;
+
+use super::{oriented_intersection_over_union, Rectangle, Square, Target};
+
+/// Represents a rectangular region rotated around its center point.
+///
+/// ### Warning
+///
+/// The [`Region`] implementation describes the **axis-aligned bounding box**
+/// of the rotated rectangle: `left`, `top`, `width` and `height` are those of
+/// the bounding box, so passing it to [`Detector::classify`](crate::Detector::classify)
+/// or other methods that sample the image **ignores the rotation**. Only
+/// [`Region::overlap`] takes the orientation into account.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RotatedRectangle {
+    pub(crate) point: Point2<f32>,
+    pub(crate) width: f32,
+    pub(crate) height: f32,
+    pub(crate) angle: f32,
+}
+
+impl RotatedRectangle {
+    /// Creates a new rotated rectangle.
+    ///
+    /// ### Arguments
+    ///
+    /// * `x`, `y` -- coordinates of the center point;
+    /// * `width`, `height` -- size of the rectangle before rotation;
+    /// * `angle` -- rotation angle in radians from the `x` axis towards the `y` axis.
+    #[inline]
+    pub fn new(x: f32, y: f32, width: f32, height: f32, angle: f32) -> Self {
+        Self {
+            point: Point2::new(x, y),
+            width,
+            height,
+            angle,
+        }
+    }
+
+    /// Returns the center point of the rectangle.
+    #[inline]
+    pub fn point(&self) -> &Point2<f32> {
+        &self.point
+    }
+
+    /// Returns the `x` coordinate of the center point.
+    #[inline]
+    pub fn x(&self) -> f32 {
+        self.point.x
+    }
+
+    /// Returns the `y` coordinate of the center point.
+    #[inline]
+    pub fn y(&self) -> f32 {
+        self.point.y
+    }
+
+    /// Returns `(width, height)` of the rectangle before rotation.
+    #[inline]
+    pub fn size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    /// Returns the rotation angle in radians.
+    #[inline]
+    pub fn angle(&self) -> f32 {
+        self.angle
+    }
+
+    /// Returns the area of the rectangle.
+    #[inline]
+    pub fn area(&self) -> f32 {
+        self.width * self.height
+    }
+
+    /// Returns corners of the rectangle in the traversal order.
+    #[inline]
+    pub fn corners(&self) -> [Point2<f32>; 4] {
+        let rotation = Rotation2::new(self.angle);
+        let (w, h) = (self.width / 2.0, self.height / 2.0);
+
+        [(-w, -h), (w, -h), (w, h), (-w, h)]
+            .map(|(x, y)| self.point + rotation * Vector2::new(x, y))
+    }
+
+    /// Returns `(left, top, right, bottom)` of the bounding box, right and bottom exclusive.
+    #[inline]
+    fn bounds(&self) -> (i32, i32, i32, i32) {
+        let corners = self.corners();
+        let (mut min, mut max) = (corners[0], corners[0]);
+
+        for corner in corners[1..].iter() {
+            min = min.inf(corner);
+            max = max.sup(corner);
+        }
+
+        (
+            ComplexField::floor(min.x) as i32,
+            ComplexField::floor(min.y) as i32,
+            ComplexField::ceil(max.x) as i32,
+            ComplexField::ceil(max.y) as i32,
+        )
+    }
+}
+
+impl Region for RotatedRectangle {
+    #[inline]
+    fn left(&self) -> i32 {
+        self.bounds().0
+    }
+
+    #[inline]
+    fn top(&self) -> i32 {
+        self.bounds().1
+    }
+
+    #[inline]
+    fn width(&self) -> u32 {
+        let (left, _, right, _) = self.bounds();
+        (right - left) as u32
+    }
+
+    #[inline]
+    fn height(&self) -> u32 {
+        let (_, top, _, bottom) = self.bounds();
+        (bottom - top) as u32
+    }
+
+    #[inline]
+    fn is_square(&self) -> bool {
+        self.width == self.height
+    }
+
+    #[inline]
+    fn center(&self) -> Point2<i32> {
+        Point2::new(
+            ComplexField::round(self.x()) as i32,
+            ComplexField::round(self.y()) as i32,
+        )
+    }
+
+    #[inline]
+    fn overlap(&self, other: &Self) -> Option<f32>
+    where
+        Self: Copy,
+    {
+        oriented_intersection_over_union(self, other)
+    }
+}
+
+impl From<Target> for RotatedRectangle {
+    #[inline]
+    fn from(value: Target) -> Self {
+        Self::new(value.x(), value.y(), value.size(), value.size(), 0.0)
+    }
+}
+
+impl From<Rectangle> for RotatedRectangle {
+    #[inline]
+    fn from(value: Rectangle) -> Self {
+        let (width, height) = (value.width as f32, value.height as f32);
+
+        Self::new(
+            value.left as f32 + width / 2.0,
+            value.top as f32 + height / 2.0,
+            width,
+            height,
+            0.0,
+        )
+    }
+}
+
+impl From<Square> for RotatedRectangle {
+    #[inline]
+    fn from(value: Square) -> Self {
+        Rectangle::from(value).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotated_rectangle_center_rounds() {
+        assert_eq!(
+            RotatedRectangle::new(10.6, 20.4, 4.0, 2.0, 0.3).center(),
+            Point2::new(11, 20)
+        );
+        assert_eq!(
+            RotatedRectangle::new(-10.6, -0.4, 4.0, 2.0, 0.0).center(),
+            Point2::new(-11, 0)
+        );
+    }
+
+    #[test]
+    fn test_rotated_rectangle_region_is_bounding_box() {
+        let bounds = |region: RotatedRectangle| {
+            (region.left(), region.top(), region.width(), region.height())
+        };
+
+        let region = RotatedRectangle::new(0.0, 0.0, 4.0, 2.0, 0.0);
+        assert_eq!(bounds(region), (-2, -1, 4, 2));
+
+        // the bounding box of the rotated square is larger than the square
+        let region = RotatedRectangle::new(0.0, 0.0, 2.0, 2.0, core::f32::consts::FRAC_PI_4);
+        assert_eq!(bounds(region), (-2, -2, 4, 4));
+    }
+}
@@ -1,3 +1,4 @@
+#[cfg(feature = "std")]
 use imageproc::rect::Rect;
 
 use crate::traits::region::Region;
@@ -84,6 +85,7 @@ impl From<(i32, i32, u32)> for Square {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<Square> for Rect {
     fn from(value: Square) -> Self {
         Self::at(value.left, value.top).of_size(value.size, value.size)
@@ -1,9 +1,10 @@
+#[cfg(feature = "std")]
 use imageproc::rect::Rect;
 use nalgebra::Point2;
 
 use crate::traits::Region;
 
-use super::Square;
+use super::{Rectangle, Square};
 
 /// Represents a target region in an image with a center point and size.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -79,6 +80,7 @@ impl Region for Target {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<Target> for Rect {
     #[inline]
     fn from(value: Target) -> Self {
@@ -86,6 +88,13 @@ impl From<Target> for Rect {
     }
 }
 
+impl From<Target> for Rectangle {
+    #[inline]
+    fn from(value: Target) -> Self {
+        Self::new(value.left(), value.top(), value.width(), value.height())
+    }
+}
+
 impl From<Target> for Square {
     #[inline]
     fn from(value: Target) -> Self {
@@ -0,0 +1,105 @@
+//! Minimal I/O used by model loaders.
+//!
+//! With `std` feature these are re-exports from [`std::io`], otherwise
+//! a subset sufficient to load models from byte slices (`&[u8]` implements [`Read`]).
+
+#[cfg(feature = "std")]
+pub use std::io::{Error, ErrorKind, Read};
+
+#[cfg(not(feature = "std"))]
+mod core_io {
+    use core::fmt;
+
+    /// A list specifying categories of loading errors.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ErrorKind {
+        /// Data is not valid for the operation.
+        InvalidData,
+        /// Data ended prematurely.
+        UnexpectedEof,
+        /// Any other error.
+        Other,
+    }
+
+    /// The error type for model loading.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Error {
+        kind: ErrorKind,
+        message: &'static str,
+    }
+
+    impl Error {
+        /// Creates an error from a known kind and a message.
+        #[inline]
+        pub fn new(kind: ErrorKind, message: &'static str) -> Self {
+            Self { kind, message }
+        }
+
+        /// Creates an error of [`ErrorKind::Other`] kind.
+        #[inline]
+        pub fn other(message: &'static str) -> Self {
+            Self::new(ErrorKind::Other, message)
+        }
+
+        /// Returns the error kind.
+        #[inline]
+        pub fn kind(&self) -> ErrorKind {
+            self.kind
+        }
+    }
+
+    impl From<ErrorKind> for Error {
+        #[inline]
+        fn from(kind: ErrorKind) -> Self {
+            Self::new(kind, "")
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{:?}: {}", self.kind, self.message)
+        }
+    }
+
+    impl core::error::Error for Error {}
+
+    /// Source of model bytes.
+    pub trait Read {
+        /// Read the exact number of bytes required to fill `buf`.
+        fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Error>;
+
+        /// Creates a "by reference" adaptor for this instance.
+        #[inline]
+        fn by_ref(&mut self) -> &mut Self
+        where
+            Self: Sized,
+        {
+            self
+        }
+    }
+
+    impl Read for &[u8] {
+        #[inline]
+        fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+            if buf.len() > self.len() {
+                *self = &self[self.len()..];
+                return Err(ErrorKind::UnexpectedEof.into());
+            }
+
+            let (head, tail) = self.split_at(buf.len());
+            buf.copy_from_slice(head);
+            *self = tail;
+            Ok(())
+        }
+    }
+
+    impl<R: Read + ?Sized> Read for &mut R {
+        #[inline]
+        fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+            (**self).read_exact(buf)
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+pub use core_io::{Error, ErrorKind, Read};
@@ -0,0 +1,17 @@
+//! Indices of the 5 face landmarks model (`face-5.shaper.bin`).
+//!
+//! Left and right sides are the sides of the face, not of the image.
+
+/// Number of landmarks.
+pub const SIZE: usize = 5;
+
+/// Outer corner of the left eye.
+pub const LEFT_EYE_OUTER: usize = 0;
+/// Inner corner of the left eye.
+pub const LEFT_EYE_INNER: usize = 1;
+/// Outer corner of the right eye.
+pub const RIGHT_EYE_OUTER: usize = 2;
+/// Inner corner of the right eye.
+pub const RIGHT_EYE_INNER: usize = 3;
+/// Base of the nose.
+pub const NOSE_BASE: usize = 4;
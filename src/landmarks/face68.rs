@@ -0,0 +1,46 @@
+//! Indices of the 68 face landmarks model in the iBUG 300-W layout.
+//!
+//! Left and right sides are the sides of the face, not of the image.
+
+use core::ops::Range;
+
+/// Number of landmarks.
+pub const SIZE: usize = 68;
+
+/// Jaw line from the right ear to the left one.
+pub const JAW: Range<usize> = 0..17;
+/// Right eyebrow.
+pub const RIGHT_EYEBROW: Range<usize> = 17..22;
+/// Left eyebrow.
+pub const LEFT_EYEBROW: Range<usize> = 22..27;
+/// Nose bridge from the top to the tip.
+pub const NOSE_BRIDGE: Range<usize> = 27..31;
+/// Bottom of the nose from the right to the left.
+pub const NOSE_BOTTOM: Range<usize> = 31..36;
+/// Right eye contour starting from the outer corner.
+pub const RIGHT_EYE: Range<usize> = 36..42;
+/// Left eye contour starting from the inner corner.
+pub const LEFT_EYE: Range<usize> = 42..48;
+/// Outer lips contour starting from the right mouth corner.
+pub const OUTER_LIPS: Range<usize> = 48..60;
+/// Inner lips contour starting from the right mouth corner.
+pub const INNER_LIPS: Range<usize> = 60..68;
+
+/// Bottom of the chin.
+pub const CHIN: usize = 8;
+/// Tip of the nose.
+pub const NOSE_TIP: usize = 30;
+/// Base of the nose.
+pub const NOSE_BASE: usize = 33;
+/// Outer corner of the right eye.
+pub const RIGHT_EYE_OUTER: usize = 36;
+/// Inner corner of the right eye.
+pub const RIGHT_EYE_INNER: usize = 39;
+/// Inner corner of the left eye.
+pub const LEFT_EYE_INNER: usize = 42;
+/// Outer corner of the left eye.
+pub const LEFT_EYE_OUTER: usize = 45;
+/// Right corner of the mouth.
+pub const MOUTH_RIGHT: usize = 48;
+/// Left corner of the mouth.
+pub const MOUTH_LEFT: usize = 54;
@@ -0,0 +1,242 @@
+pub mod face5;
+pub mod face68;
+
+use alloc::vec::Vec;
+
+use nalgebra::Point2;
+
+use crate::geometry::Target;
+
+/// Known landmarks layouts of shaper models.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LandmarkLayout {
+    /// 5 face landmarks, see [`face5`].
+    Face5,
+    /// 68 face landmarks in the iBUG 300-W layout, see [`face68`].
+    Face68,
+}
+
+impl LandmarkLayout {
+    /// Find the layout by the number of landmarks.
+    #[inline]
+    pub fn from_size(size: usize) -> Option<Self> {
+        match size {
+            face5::SIZE => Some(Self::Face5),
+            face68::SIZE => Some(Self::Face68),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of landmarks in the layout.
+    #[inline]
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Face5 => face5::SIZE,
+            Self::Face68 => face68::SIZE,
+        }
+    }
+}
+
+/// Face landmarks estimated by [`crate::Shaper`] with a known layout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Landmarks {
+    layout: LandmarkLayout,
+    points: Vec<Point2<f32>>,
+}
+
+impl Landmarks {
+    /// Wrap shaper output, returns `None` if the layout is unknown.
+    #[inline]
+    pub fn new(points: Vec<Point2<f32>>) -> Option<Self> {
+        LandmarkLayout::from_size(points.len()).map(|layout| Self { layout, points })
+    }
+
+    /// Returns the landmarks layout.
+    #[inline]
+    pub fn layout(&self) -> LandmarkLayout {
+        self.layout
+    }
+
+    /// Returns all landmark points.
+    #[inline]
+    pub fn points(&self) -> &[Point2<f32>] {
+        &self.points
+    }
+
+    /// Unwrap landmark points.
+    #[inline]
+    pub fn into_points(self) -> Vec<Point2<f32>> {
+        self.points
+    }
+
+    #[inline]
+    fn corners(&self, layout: [(usize, usize); 2]) -> (&Point2<f32>, &Point2<f32>) {
+        let (inner, outer) = match self.layout {
+            LandmarkLayout::Face5 => layout[0],
+            LandmarkLayout::Face68 => layout[1],
+        };
+        (&self.points[inner], &self.points[outer])
+    }
+
+    /// Returns inner and outer corners of the left eye.
+    #[inline]
+    pub fn left_eye_corners(&self) -> (&Point2<f32>, &Point2<f32>) {
+        self.corners([
+            (face5::LEFT_EYE_INNER, face5::LEFT_EYE_OUTER),
+            (face68::LEFT_EYE_INNER, face68::LEFT_EYE_OUTER),
+        ])
+    }
+
+    /// Returns inner and outer corners of the right eye.
+    #[inline]
+    pub fn right_eye_corners(&self) -> (&Point2<f32>, &Point2<f32>) {
+        self.corners([
+            (face5::RIGHT_EYE_INNER, face5::RIGHT_EYE_OUTER),
+            (face68::RIGHT_EYE_INNER, face68::RIGHT_EYE_OUTER),
+        ])
+    }
+
+    /// Returns centers of the left and right eyes.
+    #[inline]
+    pub fn eye_centers(&self) -> (Point2<f32>, Point2<f32>) {
+        let (li, lo) = self.left_eye_corners();
+        let (ri, ro) = self.right_eye_corners();
+        (nalgebra::center(li, lo), nalgebra::center(ri, ro))
+    }
+
+    /// Returns the left eye region to run pupil [`crate::Localizer`] on.
+    #[inline]
+    pub fn left_eye(&self) -> Target {
+        let (inner, outer) = self.left_eye_corners();
+        eye_region(inner, outer)
+    }
+
+    /// Returns the right eye region to run pupil [`crate::Localizer`] on.
+    #[inline]
+    pub fn right_eye(&self) -> Target {
+        let (inner, outer) = self.right_eye_corners();
+        eye_region(inner, outer)
+    }
+
+    /// Returns the nose region spanning from the nose bridge to the nose base.
+    #[inline]
+    pub fn nose(&self) -> Target {
+        let top = match self.layout {
+            LandmarkLayout::Face5 => nalgebra::center(
+                &self.points[face5::LEFT_EYE_INNER],
+                &self.points[face5::RIGHT_EYE_INNER],
+            ),
+            LandmarkLayout::Face68 => self.points[face68::NOSE_BRIDGE.start],
+        };
+        let base = match self.layout {
+            LandmarkLayout::Face5 => &self.points[face5::NOSE_BASE],
+            LandmarkLayout::Face68 => &self.points[face68::NOSE_BASE],
+        };
+
+        let center = nalgebra::center(&top, base);
+        Target::new(center.x, center.y, nalgebra::distance(&top, base))
+    }
+
+    /// Returns the mouth region, `None` if the layout has no mouth landmarks.
+    #[inline]
+    pub fn mouth(&self) -> Option<Target> {
+        match self.layout {
+            LandmarkLayout::Face5 => None,
+            LandmarkLayout::Face68 => {
+                let lips = &self.points[face68::OUTER_LIPS];
+                let center = lips
+                    .iter()
+                    .map(|p| p.coords)
+                    .sum::<nalgebra::Vector2<f32>>()
+                    / lips.len() as f32;
+                let width = nalgebra::distance(
+                    &self.points[face68::MOUTH_RIGHT],
+                    &self.points[face68::MOUTH_LEFT],
+                );
+
+                Some(Target::new(center.x, center.y, width * 1.1))
+            }
+        }
+    }
+}
+
+impl AsRef<[Point2<f32>]> for Landmarks {
+    #[inline]
+    fn as_ref(&self) -> &[Point2<f32>] {
+        &self.points
+    }
+}
+
+impl From<Landmarks> for Vec<Point2<f32>> {
+    #[inline]
+    fn from(value: Landmarks) -> Self {
+        value.points
+    }
+}
+
+/// Eye region centered between the corners and slightly wider than the eye.
+#[inline]
+fn eye_region(inner: &Point2<f32>, outer: &Point2<f32>) -> Target {
+    let center = nalgebra::center(inner, outer);
+    Target::new(center.x, center.y, nalgebra::distance(inner, outer) * 1.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn face5() -> Landmarks {
+        Landmarks::new(vec![
+            Point2::new(340.0, 270.0),
+            Point2::new(320.0, 270.0),
+            Point2::new(250.0, 266.0),
+            Point2::new(280.0, 270.0),
+            Point2::new(300.0, 330.0),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_landmark_layout_from_size() {
+        assert_eq!(LandmarkLayout::from_size(5), Some(LandmarkLayout::Face5));
+        assert_eq!(LandmarkLayout::from_size(68), Some(LandmarkLayout::Face68));
+        assert_eq!(LandmarkLayout::from_size(7), None);
+        assert!(Landmarks::new(vec![Point2::origin(); 7]).is_none());
+    }
+
+    #[test]
+    fn test_landmarks_face5_regions() {
+        let landmarks = face5();
+
+        let left = landmarks.left_eye();
+        assert_abs_diff_eq!(*left.point(), Point2::new(330.0, 270.0));
+        assert_abs_diff_eq!(left.size(), 22.0, epsilon = 1e-4);
+
+        let right = landmarks.right_eye();
+        assert_abs_diff_eq!(*right.point(), Point2::new(265.0, 268.0));
+
+        let (lc, rc) = landmarks.eye_centers();
+        assert_abs_diff_eq!(lc, *left.point());
+        assert_abs_diff_eq!(rc, *right.point());
+
+        let nose = landmarks.nose();
+        assert_abs_diff_eq!(*nose.point(), Point2::new(300.0, 300.0));
+        assert_abs_diff_eq!(nose.size(), 60.0, epsilon = 1e-4);
+
+        assert!(landmarks.mouth().is_none());
+    }
+
+    #[test]
+    fn test_landmarks_face68_mouth() {
+        let mut points = vec![Point2::new(0.0, 0.0); face68::SIZE];
+        for (i, index) in face68::OUTER_LIPS.enumerate() {
+            let angle = i as f32 * core::f32::consts::PI / 6.0;
+            points[index] = Point2::new(100.0 - 20.0 * angle.cos(), 200.0 + 10.0 * angle.sin());
+        }
+
+        let mouth = Landmarks::new(points).unwrap().mouth().unwrap();
+
+        assert_abs_diff_eq!(*mouth.point(), Point2::new(100.0, 200.0), epsilon = 1e-4);
+        assert_abs_diff_eq!(mouth.size(), 44.0, epsilon = 1e-4);
+    }
+}
@@ -1,31 +1,77 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(all(test, not(feature = "std")))]
+#[macro_use]
+extern crate std;
+
+#[cfg(feature = "std")]
 pub extern crate image;
-pub extern crate pixelutil_image;
+#[cfg(feature = "std")]
 pub extern crate imageproc;
 pub extern crate nalgebra;
+#[cfg(feature = "std")]
+pub extern crate pixelutil_image;
 pub extern crate rand;
 
 extern crate similarity_least_squares;
 
+#[cfg(feature = "std")]
 extern crate derive_builder;
 
 #[cfg(test)]
 #[macro_use]
 extern crate approx;
 
+mod border;
 mod geometry;
 mod nodes;
 mod traits;
 
+#[cfg(feature = "std")]
+mod chip;
 mod detect;
 mod localize;
+#[cfg(feature = "std")]
+mod pipeline;
+#[cfg(feature = "std")]
+mod pose;
 mod shape;
+mod view;
+
+#[cfg(feature = "std")]
+pub mod codegen;
+pub mod io;
+pub mod landmarks;
 
-pub use geometry::{Square, Target};
+#[cfg(feature = "bundled-models")]
+pub mod bundled;
 
+pub use border::BorderMode;
+#[cfg(feature = "std")]
+pub use chip::{chip_transform, extract_chip, ChipExtractor, ChipTemplate};
+pub use geometry::{
+    intersection_over_union, oriented_intersection_over_union, Rectangle, RotatedRectangle, Square,
+    Target,
+};
+
+pub use detect::{clusterize, reference, Detection, Detector, DetectorRef, StaticDetector};
+#[cfg(feature = "std")]
 pub use detect::{
-    clusterize, multiscale, DetectMultiscale, DetectMultiscaleBuilder,
-    DetectMultiscaleBuilderError, Detection, Detector, Padding,
+    multiscale, DetectMultiscale, DetectMultiscaleBuilder, DetectMultiscaleBuilderError,
+    MultiDetector, Padding,
 };
+pub use landmarks::{LandmarkLayout, Landmarks};
 pub use localize::{perturbate, LocalizePerturbate, Localizer};
-pub use shape::Shaper;
-pub use traits::Region;
+#[cfg(feature = "std")]
+pub use pipeline::{Face, FacePipeline, FacePipelineBuilder, FacePipelineBuilderError};
+#[cfg(feature = "std")]
+pub use pose::{Camera, FaceTemplate, HeadPose, HeadPoseError, HeadPoseEstimator};
+pub use shape::{Interpolation, Normalization, ShapePerturbate, Shaper};
+pub use traits::{GrayImageView, Luminance, Region};
+#[cfg(feature = "std")]
+pub use view::{ColorView, GenericView};
+pub use view::{
+    GrayView, LumaCoefficients, PackedFormat, PackedView, ViewError, YuvFormat, YuvView,
+};
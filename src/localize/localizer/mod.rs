@@ -1,12 +1,13 @@
-use std::fmt::Debug;
-use std::io::{Error, Read};
+use alloc::vec::Vec;
+use core::fmt::Debug;
 
-use image::Luma;
 use nalgebra::{Point2, Translation2, Vector2};
-use pixelutil_image::ExtendedImageView;
 
+use crate::border::BorderMode;
 use crate::geometry::Target;
+use crate::io::{Error, Read};
 use crate::nodes::ComparisonNode;
+use crate::traits::GrayImageView;
 
 type Tree = Vec<ComparisonNode>;
 type Predictions = Vec<Vector2<f32>>;
@@ -20,21 +21,35 @@ pub struct Localizer {
     depth: usize,
     dsize: usize,
     scale: f32,
+    border: BorderMode,
     stages: Vec<Stage>,
 }
 
 impl Debug for Localizer {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct(stringify!(Localizer))
             .field("depth", &self.depth)
             .field("dsize", &self.dsize)
             .field("scale", &self.scale)
+            .field("border", &self.border)
             .field("stages", &self.stages.len())
             .finish()
     }
 }
 
 impl Localizer {
+    /// Returns the handling of samples outside of the image.
+    #[inline]
+    pub fn border(&self) -> BorderMode {
+        self.border
+    }
+
+    /// Set the handling of samples outside of the image.
+    #[inline]
+    pub fn set_border(&mut self, value: BorderMode) {
+        self.border = value;
+    }
+
     // TODO:
     /// Estimate object location on the image
     ///
@@ -42,10 +57,13 @@ impl Localizer {
     ///
     /// * `image` - Target image.
     /// * `roi` - Region of interest, which is the initial guess of the object location.
+    ///
+    /// With [`BorderMode::Reject`] refinement stops at the first stage
+    /// with the window crossing the image border.
     #[inline]
     pub fn localize<I>(&self, image: &I, roi: Target) -> Point2<f32>
     where
-        I: ExtendedImageView<Pixel = Luma<u8>>,
+        I: GrayImageView,
     {
         let Target {
             mut point,
@@ -53,18 +71,26 @@ impl Localizer {
         } = roi;
 
         for stage in self.stages.iter() {
-            let mut translation = Translation2::identity();
             let p = unsafe { point.coords.try_cast::<i32>().unwrap_unchecked() }.into();
-            let s = size as u32;
-
-            for (codes, preds) in stage.iter() {
-                let idx = (0..self.depth).fold(0, |idx, _| {
-                    2 * idx + 1 + codes[idx].bintest(image, p, s) as usize
-                });
-                let lutidx = (idx + 1) - self.dsize;
+            let s = (size as u32, size as u32);
+
+            let translation = if ComparisonNode::interior(image.size(), p, s) {
+                // SAFETY: all node offsets of the window are inside the image.
+                self.stage(stage, |node| unsafe {
+                    Some(node.bintest_unchecked(image, p, s))
+                })
+            } else {
+                match self.border {
+                    BorderMode::Clamp => self.stage(stage, |node| Some(node.bintest(image, p, s))),
+                    border => self.stage(stage, |node| node.bintest_border(image, p, s, border)),
+                }
+            };
 
-                translation.vector += preds[lutidx];
-            }
+            // the window is rejected by the border mode
+            let mut translation = match translation {
+                Some(translation) => translation,
+                None => break,
+            };
 
             translation.vector.scale_mut(size);
             *point = *translation.transform_point(&point);
@@ -74,6 +100,29 @@ impl Localizer {
         point
     }
 
+    /// Translation predicted by the trees of the stage,
+    /// `None` if `bintest` rejects the window.
+    #[inline(always)]
+    fn stage<F>(&self, stage: &Stage, bintest: F) -> Option<Translation2<f32>>
+    where
+        F: Fn(&ComparisonNode) -> Option<bool>,
+    {
+        let mut translation = Translation2::identity();
+
+        for (codes, preds) in stage.iter() {
+            let mut idx = 0;
+
+            for _ in 0..self.depth {
+                idx = 2 * idx + 1 + bintest(&codes[idx])? as usize;
+            }
+            let lutidx = (idx + 1) - self.dsize;
+
+            translation.vector += preds[lutidx];
+        }
+
+        Some(translation)
+    }
+
     /// Load localizer from a readable source.
     #[inline]
     pub fn load(mut readable: impl Read) -> Result<Self, Error> {
@@ -130,6 +179,7 @@ impl Localizer {
             depth,
             dsize: pred_size,
             scale,
+            border: BorderMode::Clamp,
             stages,
         })
     }
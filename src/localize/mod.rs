@@ -1,14 +1,15 @@
 mod localizer;
 pub mod perturbate;
 
-use image::Luma;
 pub use localizer::Localizer;
 
+use alloc::vec::Vec;
+
 use nalgebra::Point2;
-use perturbate::Perturbator;
-use pixelutil_image::ExtendedImageView;
+use perturbate::{HaltonPerturbator, Perturbator};
 use rand::RngCore;
 
+use crate::traits::GrayImageView;
 use crate::Target;
 
 /// Implements object localization with perturbation.
@@ -53,23 +54,113 @@ impl LocalizePerturbate {
     ) -> Point2<f32>
     where
         R: RngCore,
-        I: ExtendedImageView<Pixel = Luma<u8>>,
+        I: GrayImageView,
+    {
+        self.localize(localizer, image, target, |f| {
+            self.perturbator.run(rng, self.runs, target, f)
+        })
+    }
+
+    /// Applies deterministic perturbations from the `perturbator` to the target
+    /// and runs the localizer on each perturbed target.
+    ///
+    /// Unlike [`LocalizePerturbate::run`] it does not need a random number generator,
+    /// so the same input always produces the same output.
+    #[inline]
+    pub fn run_halton<I>(
+        &self,
+        localizer: &Localizer,
+        perturbator: &HaltonPerturbator,
+        image: &I,
+        target: Target,
+    ) -> Point2<f32>
+    where
+        I: GrayImageView,
+    {
+        self.localize(localizer, image, target, |f| {
+            perturbator.run(self.runs, target, f)
+        })
+    }
+
+    /// Median of localizations of perturbed targets, or the localization
+    /// of the target itself if there are no perturbations.
+    #[inline]
+    fn localize<I, P>(
+        &self,
+        localizer: &Localizer,
+        image: &I,
+        target: Target,
+        perturbate: P,
+    ) -> Point2<f32>
+    where
+        I: GrayImageView,
+        P: FnOnce(&mut dyn FnMut(Target)),
     {
         let mut xs: Vec<f32> = Vec::with_capacity(self.runs);
         let mut ys: Vec<f32> = Vec::with_capacity(self.runs);
 
-        self.perturbator.run(rng, self.runs, target, |t| {
+        perturbate(&mut |t| {
             let p = localizer.localize(image, t);
 
             xs.push(p.x);
             ys.push(p.y);
         });
 
+        if xs.is_empty() {
+            return localizer.localize(image, target);
+        }
+
         xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
         ys.sort_by(|a, b| a.partial_cmp(b).unwrap());
 
-        let index = (self.runs - 1) / 2;
+        let index = (xs.len() - 1) / 2;
 
         Point2::new(xs[index], ys[index])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_xoshiro::Xoroshiro128PlusPlus;
+
+    use crate::view::GrayView;
+
+    use super::*;
+
+    /// Localizer of one stump shifting the target by `(dx, dy)` of its size.
+    fn localizer(dx: f32, dy: f32) -> Localizer {
+        let mut data = Vec::new();
+        data.extend(1i32.to_le_bytes());
+        data.extend(1.0f32.to_le_bytes());
+        data.extend(1i32.to_le_bytes());
+        data.extend(1i32.to_le_bytes());
+        data.extend([0, 0, 0, 0]);
+        for _ in 0..2 {
+            data.extend(dy.to_le_bytes());
+            data.extend(dx.to_le_bytes());
+        }
+
+        Localizer::load(data.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_localize_perturbate_zero_runs() {
+        let localizer = localizer(0.1, -0.2);
+        let data = [128u8; 32 * 32];
+        let image = GrayView::packed(&data, 32, 32).unwrap();
+        let target = Target::new(16.0, 16.0, 10.0);
+
+        let expected = localizer.localize(&image, target);
+        assert_ne!(expected, Point2::new(16.0, 16.0));
+
+        let localize = LocalizePerturbate::new(0);
+        let mut rng = Xoroshiro128PlusPlus::seed_from_u64(42);
+
+        assert_eq!(localize.run(&localizer, &mut rng, &image, target), expected);
+        assert_eq!(
+            localize.run_halton(&localizer, &Default::default(), &image, target),
+            expected
+        );
+    }
+}
@@ -1,8 +1,9 @@
-use std::{
+use core::{
     convert::{TryFrom, TryInto},
     ops::Range,
 };
 
+use nalgebra::ComplexField;
 use rand::{
     distr::{uniform::Error, Uniform},
     Rng, RngCore,
@@ -80,9 +81,153 @@ pub fn perturbate<R, F>(
     for _ in 0..count {
         let s = size * rng.sample(scale);
 
-        let x = s.mul_add(rng.sample(translate), init.x());
-        let y = s.mul_add(rng.sample(translate), init.y());
+        let x = ComplexField::mul_add(s, rng.sample(translate), init.x());
+        let y = ComplexField::mul_add(s, rng.sample(translate), init.y());
 
         f(Target::new(x, y, s));
     }
 }
+
+/// A deterministic perturbator sampling a target's size and position from
+/// the Halton low-discrepancy sequence instead of a random number generator.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HaltonPerturbator {
+    pub scale: Range<f32>,
+    pub translate: Range<f32>,
+}
+
+impl HaltonPerturbator {
+    /// Creates a new perturbator with the specified scale and translation ranges.
+    #[inline]
+    pub fn from_ranges(scale: Range<f32>, translate: Range<f32>) -> Result<Self, Error> {
+        Uniform::<f32>::try_from(scale.clone())?;
+        Uniform::<f32>::try_from(translate.clone())?;
+
+        Ok(Self { scale, translate })
+    }
+
+    /// Applies perturbations to a target's size and position, calling the provided closure
+    /// `f` with each perturbed target. See the [`halton_perturbate`] function for more details.
+    #[inline]
+    pub fn run<F>(&self, count: usize, init: Target, f: F)
+    where
+        F: FnMut(Target),
+    {
+        halton_perturbate(self.scale.clone(), self.translate.clone(), count, init, f)
+    }
+}
+
+impl Default for HaltonPerturbator {
+    /// Creates a default perturbator with the same ranges as [`Perturbator::default`].
+    #[inline]
+    fn default() -> Self {
+        Self {
+            scale: 0.925..0.94,
+            translate: -0.075..0.075,
+        }
+    }
+}
+
+/// Applies deterministic perturbations to a target's size and position, calling
+/// the provided closure `f` with each perturbed target.
+///
+/// The scale and the `x`, `y` translations are taken from the Halton sequence
+/// with bases 2, 3 and 5 respectively, so the same input always produces
+/// the same targets evenly covering the ranges.
+///
+/// # Arguments
+///
+/// * `scale` - A range for scaling the target's size.
+/// * `translate` - A range for translating the target's position.
+/// * `count` - The number of perturbations to apply.
+/// * `init` - The initial target to perturb.
+#[inline]
+pub fn halton_perturbate<F>(
+    scale: Range<f32>,
+    translate: Range<f32>,
+    count: usize,
+    init: Target,
+    mut f: F,
+) where
+    F: FnMut(Target),
+{
+    let size = init.size();
+
+    let lerp =
+        |range: &Range<f32>, t: f32| ComplexField::mul_add(range.end - range.start, t, range.start);
+
+    for index in 1..=count {
+        let s = size * lerp(&scale, halton(index, 2));
+
+        let x = ComplexField::mul_add(s, lerp(&translate, halton(index, 3)), init.x());
+        let y = ComplexField::mul_add(s, lerp(&translate, halton(index, 5)), init.y());
+
+        f(Target::new(x, y, s));
+    }
+}
+
+/// Returns `index`-th element of the van der Corput sequence in the specified `base`.
+#[inline]
+fn halton(mut index: usize, base: usize) -> f32 {
+    let mut result = 0.0f32;
+    let mut fraction = 1.0f32;
+
+    while index > 0 {
+        fraction /= base as f32;
+        result += fraction * (index % base) as f32;
+        index /= base;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn test_halton() {
+        let base2: Vec<f32> = (1..=4).map(|i| halton(i, 2)).collect();
+        let base3: Vec<f32> = (1..=4).map(|i| halton(i, 3)).collect();
+
+        assert_abs_diff_eq!(base2.as_slice(), [0.5, 0.25, 0.75, 0.125].as_slice());
+        assert_abs_diff_eq!(
+            base3.as_slice(),
+            [1.0 / 3.0, 2.0 / 3.0, 1.0 / 9.0, 4.0 / 9.0].as_slice()
+        );
+    }
+
+    #[test]
+    fn test_halton_perturbate_deterministic() {
+        let perturbator = HaltonPerturbator::default();
+        let init = Target::new(100.0, 100.0, 50.0);
+
+        let mut first = Vec::new();
+        let mut second = Vec::new();
+
+        perturbator.run(31, init, |t| first.push(t));
+        perturbator.run(31, init, |t| second.push(t));
+
+        assert_eq!(first.len(), 31);
+        assert_eq!(first, second);
+
+        for target in first.iter() {
+            let scale = target.size() / init.size();
+            assert!(perturbator.scale.contains(&scale));
+
+            let dx = (target.x() - init.x()) / target.size();
+            let dy = (target.y() - init.y()) / target.size();
+            assert!(perturbator.translate.start <= dx && dx <= perturbator.translate.end);
+            assert!(perturbator.translate.start <= dy && dy <= perturbator.translate.end);
+        }
+    }
+
+    #[test]
+    fn test_halton_perturbator_from_ranges() {
+        assert!(HaltonPerturbator::from_ranges(0.9..1.1, -0.1..0.1).is_ok());
+        assert!(HaltonPerturbator::from_ranges(1.1..0.9, -0.1..0.1).is_err());
+        assert!(HaltonPerturbator::from_ranges(0.9..1.1, 0.1..0.1).is_err());
+    }
+}
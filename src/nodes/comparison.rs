@@ -1,7 +1,7 @@
-use image::Luma;
 use nalgebra::Point2;
 
-use pixelutil_image::ExtendedImageView;
+use crate::border::BorderMode;
+use crate::traits::GrayImageView;
 
 #[derive(Debug, PartialEq, Clone, Copy, Default)]
 pub struct ComparisonNode(pub Point2<i8>, pub Point2<i8>);
@@ -42,20 +42,67 @@ impl From<ComparisonNode> for [u8; 4] {
 }
 
 impl ComparisonNode {
+    /// Compare pixels at node offsets of the window at `point` scaled
+    /// by `(width, height)`, clamped to the image bounds.
     #[inline]
-    pub fn bintest<I: ExtendedImageView<Pixel = Luma<u8>>>(
+    pub fn bintest<I: GrayImageView>(
         &self,
         image: &I,
         point: Point2<i32>,
-        size: u32,
+        scale: (u32, u32),
     ) -> bool {
-        let p0 = transform(point, size, self.0.cast());
-        let p1 = transform(point, size, self.1.cast());
+        let p0 = transform(point, scale, self.0.cast());
+        let p1 = transform(point, scale, self.1.cast());
 
-        let Luma([lum0]) = image.get_pixel_clamped(p0);
-        let Luma([lum1]) = image.get_pixel_clamped(p1);
+        image.luma_clamped(p0) > image.luma_clamped(p1)
+    }
+
+    /// Same as [`bintest`](Self::bintest) but samples outside of the image
+    /// are handled with the border mode, `None` if the window is rejected.
+    #[inline]
+    pub fn bintest_border<I: GrayImageView>(
+        &self,
+        image: &I,
+        point: Point2<i32>,
+        scale: (u32, u32),
+        border: BorderMode,
+    ) -> Option<bool> {
+        let p0 = transform(point, scale, self.0.cast());
+        let p1 = transform(point, scale, self.1.cast());
 
-        lum0 > lum1
+        Some(image.luma_border(p0, border)? > image.luma_border(p1, border)?)
+    }
+
+    /// Same as [`bintest`](Self::bintest) but without clamping to the image bounds.
+    ///
+    /// # Safety
+    ///
+    /// The window should be inside the image, see [`interior`](Self::interior).
+    #[inline]
+    pub unsafe fn bintest_unchecked<I: GrayImageView>(
+        &self,
+        image: &I,
+        point: Point2<i32>,
+        scale: (u32, u32),
+    ) -> bool {
+        let p0 = transform(point, scale, self.0.cast());
+        let p1 = transform(point, scale, self.1.cast());
+
+        image.luma_unchecked(p0.x as u32, p0.y as u32)
+            > image.luma_unchecked(p1.x as u32, p1.y as u32)
+    }
+
+    /// Checks that every node offset of the window at `point` scaled by `scale`
+    /// is sampled inside the image with `(width, height)` dimensions.
+    ///
+    /// Offsets are computed without overflow, so windows of any size are checked.
+    #[inline]
+    pub fn interior(dimensions: (u32, u32), point: Point2<i32>, scale: (u32, u32)) -> bool {
+        let (width, height) = (dimensions.0 as i64, dimensions.1 as i64);
+        let min = transform_wide(point, scale, Point2::new(i8::MIN, i8::MIN).cast());
+        let max = transform_wide(point, scale, Point2::new(i8::MAX, i8::MAX).cast());
+
+        min.x >= 0 && min.y >= 0 && max.x < width && max.y < height
     }
 }
 
@@ -69,9 +116,21 @@ fn na_transform(i: Point2<i32>, s: u32, n: Point2<i32>) -> Point2<i32> {
     (i * SCALE + n.coords * (s as i32)) / SCALE
 }
 
+/// Same as [`original_transform`] with independent horizontal and vertical scales.
+///
+/// Coordinates beyond the `i32` range are saturated, so they stay outside of any image.
 #[inline]
-fn transform(i: Point2<i32>, s: u32, n: Point2<i32>) -> Point2<i32> {
-    let (x, y) = original_transform(i.x, i.y, s as i32, n.x, n.y);
+fn transform(i: Point2<i32>, s: (u32, u32), n: Point2<i32>) -> Point2<i32> {
+    let saturate = |value: i64| value.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+    let p = transform_wide(i, s, n);
+    Point2::new(saturate(p.x), saturate(p.y))
+}
+
+/// Same as [`transform`] computed in `i64`, which can not overflow.
+#[inline]
+fn transform_wide(i: Point2<i32>, s: (u32, u32), n: Point2<i32>) -> Point2<i64> {
+    let x = (((i.x as i64) << SHIFT) + n.x as i64 * s.0 as i64) >> SHIFT;
+    let y = (((i.y as i64) << SHIFT) + n.y as i64 * s.1 as i64) >> SHIFT;
     Point2::new(x, y)
 }
 
@@ -83,7 +142,7 @@ fn original_transform(ix: i32, iy: i32, s: i32, nx: i32, ny: i32) -> (i32, i32)
     (x, y)
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use image::{GrayImage, Luma};
 
@@ -92,7 +151,7 @@ mod tests {
     #[test]
     fn test_comparison_node_from_into() {
         let data: [i8; 4] = [-128, 42, -34, 127];
-        let buf: [u8; 4] = ComparisonNode::from(data.clone()).into();
+        let buf: [u8; 4] = ComparisonNode::from(data).into();
         let out: [i8; 4] = ComparisonNode::from(buf).into();
         assert_eq!(data, out);
     }
@@ -109,7 +168,16 @@ mod tests {
         let i = Point2::new(100, 150);
         let p = Point2::new(42, -34);
         let s = 50;
-        assert_eq!(na_transform(i, s, p), transform(i, s, p));
+        assert_eq!(na_transform(i, s, p), transform(i, (s, s), p));
+    }
+
+    #[test]
+    fn test_transform_rectangular() {
+        let i = Point2::new(100, 150);
+        let p = Point2::new(42, -34);
+        let (x, _) = original_transform(i.x, i.y, 50, p.x, p.y);
+        let (_, y) = original_transform(i.x, i.y, 120, p.x, p.y);
+        assert_eq!(transform(i, (50, 120), p), Point2::new(x, y));
     }
 
     #[test]
@@ -122,6 +190,81 @@ mod tests {
         image.put_pixel(size - 1, size - 1, Luma::from([255u8]));
 
         let point = Point2::new(size / 2 + 1, size / 2 + 1);
-        assert!(node.bintest(&image, point.cast(), size));
+        assert!(node.bintest(&image, point.cast(), (size, size)));
+    }
+
+    #[test]
+    fn test_comparison_node_interior() {
+        let dimensions = (100, 80);
+
+        assert!(ComparisonNode::interior(
+            dimensions,
+            Point2::new(50, 40),
+            (60, 60)
+        ));
+        assert!(ComparisonNode::interior(
+            dimensions,
+            Point2::new(50, 40),
+            (80, 80)
+        ));
+        assert!(!ComparisonNode::interior(
+            dimensions,
+            Point2::new(50, 40),
+            (80, 82)
+        ));
+        assert!(!ComparisonNode::interior(
+            dimensions,
+            Point2::new(10, 40),
+            (40, 40)
+        ));
+        assert!(!ComparisonNode::interior(
+            dimensions,
+            Point2::new(50, 70),
+            (40, 40)
+        ));
+
+        let image = GrayImage::from_fn(100, 80, |x, y| Luma([(x * 7 + y * 13) as u8]));
+        let point = Point2::new(60, 35);
+        let size = (64, 48);
+        assert!(ComparisonNode::interior(dimensions, point, size));
+
+        for data in [
+            [-128i8, -128, 127, 127],
+            [127, -128, -128, 127],
+            [42, -34, 0, 9],
+        ] {
+            let node = ComparisonNode::from(data);
+            let unchecked = unsafe { node.bintest_unchecked(&image, point, size) };
+            assert_eq!(node.bintest(&image, point, size), unchecked);
+        }
+    }
+
+    #[test]
+    fn test_comparison_node_interior_overflow() {
+        let dimensions = (4_200_000, 10);
+
+        // offsets wrap around in `i32` arithmetic
+        assert!(!ComparisonNode::interior(
+            dimensions,
+            Point2::new(-4_194_305, 4),
+            (1 << 24, 2)
+        ));
+        assert!(!ComparisonNode::interior(
+            dimensions,
+            Point2::new(i32::MAX, i32::MAX),
+            (u32::MAX, u32::MAX)
+        ));
+        assert!(!ComparisonNode::interior(
+            (u32::MAX, u32::MAX),
+            Point2::new(i32::MIN, 0),
+            (1, 1)
+        ));
+
+        let node = ComparisonNode::from([0i8, i8::MIN, 0, i8::MAX]);
+        let point = Point2::new(i32::MAX - 1, 0);
+        let p0 = transform(point, (u32::MAX, 1), node.0.cast());
+        let p1 = transform(point, (u32::MAX, 1), node.1.cast());
+        assert!(p0.x < 0);
+        assert_eq!(p1.x, i32::MAX);
     }
 }
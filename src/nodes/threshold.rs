@@ -1,5 +1,5 @@
-use std::convert::TryInto;
-use std::mem::{transmute, MaybeUninit};
+use core::convert::TryInto;
+use core::mem::{transmute, MaybeUninit};
 
 #[derive(Debug, Clone, Copy)]
 pub struct ThresholdNode {
@@ -0,0 +1,20 @@
+use nalgebra::Point2;
+
+use crate::geometry::Target;
+use crate::landmarks::Landmarks;
+
+/// Face found by [`super::FacePipeline`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Face {
+    /// Detection score.
+    pub score: f32,
+    /// Detected face region.
+    pub region: Target,
+    /// Face landmarks.
+    pub landmarks: Landmarks,
+    /// Spread of each landmark, available when shaping with perturbation.
+    /// See [`crate::ShapePerturbate::run_with_spread`].
+    pub landmarks_spread: Option<Vec<f32>>,
+    /// Left and right eye pupils.
+    pub pupils: (Point2<f32>, Point2<f32>),
+}
@@ -0,0 +1,226 @@
+mod face;
+
+use derive_builder::Builder;
+use rand::RngCore;
+
+use crate::detect::{DetectMultiscale, Detection, Detector};
+use crate::geometry::Target;
+use crate::landmarks::{LandmarkLayout, Landmarks};
+use crate::localize::{LocalizePerturbate, Localizer};
+use crate::shape::{ShapePerturbate, Shaper};
+use crate::traits::GrayImageView;
+
+pub use face::Face;
+
+/// Face analysis running detection with [`Detector`], landmarks estimation
+/// with [`Shaper`] and pupils localization with [`Localizer`] on each face.
+#[derive(Debug, Clone, Builder)]
+#[builder(pattern = "owned", build_fn(validate = "Self::validate"))]
+pub struct FacePipeline {
+    /// Face detector.
+    detector: Detector,
+    /// Face landmarks shaper with a known [`LandmarkLayout`].
+    shaper: Shaper,
+    /// Eye pupil localizer.
+    localizer: Localizer,
+    /// Face detection parameters.
+    pub detect: DetectMultiscale,
+    /// Landmarks estimation with perturbation parameters,
+    /// shaper runs once on the detected region if not set.
+    #[builder(default)]
+    pub shape: Option<ShapePerturbate>,
+    /// Pupils localization parameters.
+    #[builder(default)]
+    pub localize: LocalizePerturbate,
+}
+
+impl FacePipelineBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match &self.shaper {
+            Some(shaper) if LandmarkLayout::from_size(shaper.size()).is_none() => Err(format!(
+                "shaper has unknown landmarks layout of size {}",
+                shaper.size()
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl FacePipeline {
+    /// Create default builder struct.
+    #[inline]
+    pub fn builder() -> FacePipelineBuilder {
+        FacePipelineBuilder::default()
+    }
+
+    /// Returns the face detector.
+    #[inline]
+    pub fn detector(&self) -> &Detector {
+        &self.detector
+    }
+
+    /// Returns the face landmarks shaper.
+    #[inline]
+    pub fn shaper(&self) -> &Shaper {
+        &self.shaper
+    }
+
+    /// Returns the eye pupil localizer.
+    #[inline]
+    pub fn localizer(&self) -> &Localizer {
+        &self.localizer
+    }
+
+    /// Find faces on the image and analyze each one.
+    #[inline]
+    pub fn run<R, I>(&self, rng: &mut R, image: &I) -> Vec<Face>
+    where
+        R: RngCore,
+        I: GrayImageView,
+    {
+        self.detect
+            .run(&self.detector, image)
+            .into_iter()
+            .map(|detection| self.analyze(rng, image, detection))
+            .collect()
+    }
+
+    /// Estimate landmarks and pupils of the detected face.
+    #[inline]
+    pub fn analyze<R, I>(&self, rng: &mut R, image: &I, detection: Detection<Target>) -> Face
+    where
+        R: RngCore,
+        I: GrayImageView,
+    {
+        let region = *detection.region();
+
+        let (points, landmarks_spread) = match self.shape {
+            Some(shape) => {
+                let (points, spread) = shape.run_with_spread(&self.shaper, rng, image, region);
+                (points, Some(spread))
+            }
+            None => (self.shaper.shape(image, region), None),
+        };
+
+        let landmarks = Landmarks::new(points).expect("shaper layout is validated by builder");
+
+        let left_pupil = self
+            .localize
+            .run(&self.localizer, rng, image, landmarks.left_eye());
+        let right_pupil = self
+            .localize
+            .run(&self.localizer, rng, image, landmarks.right_eye());
+
+        Face {
+            score: detection.score(),
+            region,
+            landmarks,
+            landmarks_spread,
+            pupils: (left_pupil, right_pupil),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::GrayImage;
+    use rand::SeedableRng;
+    use rand_xoshiro::Xoroshiro128PlusPlus;
+
+    use crate::detect::model_bytes;
+    use crate::detect::multiscale::Multiscaler;
+
+    use super::*;
+
+    fn detector() -> Detector {
+        let data = model_bytes(1, [(vec![[0; 4]], vec![1.0, 1.0], -1.0)]);
+        Detector::load(data.as_slice()).unwrap()
+    }
+
+    fn localizer() -> Localizer {
+        let mut data = Vec::new();
+        data.extend(1i32.to_le_bytes());
+        data.extend(0.8f32.to_le_bytes());
+        data.extend(1i32.to_le_bytes());
+        data.extend(1i32.to_le_bytes());
+        data.extend([0u8; 4 + 2 * 2 * 4]);
+        Localizer::load(data.as_slice()).unwrap()
+    }
+
+    fn shaper(shape: &[f32]) -> Shaper {
+        let mut data = vec![1u8];
+        data.extend(2u32.to_be_bytes());
+        data.extend((shape.len() as u32 / 2).to_be_bytes());
+        data.extend(
+            [0u32, 0u32, 1u32, 0u32]
+                .iter()
+                .flat_map(|v| v.to_be_bytes()),
+        );
+        data.extend(shape.iter().flat_map(|v| v.to_be_bytes()));
+        Shaper::load(data.as_slice()).unwrap()
+    }
+
+    fn face5_shaper() -> Shaper {
+        shaper(&[0.8, 0.3, 0.6, 0.3, 0.2, 0.3, 0.4, 0.3, 0.5, 0.6])
+    }
+
+    fn detect() -> DetectMultiscale {
+        DetectMultiscale::builder()
+            .multiscaler(Multiscaler::new(32, 64, 0.5, 1.5).unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_face_pipeline_run() {
+        let image = GrayImage::from_fn(64, 64, |x, y| image::Luma([(x * y % 256) as u8]));
+        let mut rng = Xoroshiro128PlusPlus::seed_from_u64(42);
+
+        let mut pipeline = FacePipeline::builder()
+            .detector(detector())
+            .shaper(face5_shaper())
+            .localizer(localizer())
+            .detect(detect())
+            .build()
+            .unwrap();
+
+        let faces = pipeline.run(&mut rng, &image);
+        assert!(!faces.is_empty());
+
+        for face in faces.iter() {
+            assert_eq!(face.landmarks.layout(), LandmarkLayout::Face5);
+            assert!(face.landmarks_spread.is_none());
+            assert_abs_diff_eq!(
+                face.pupils.0,
+                *face.landmarks.left_eye().point(),
+                epsilon = 1.0
+            );
+            assert_abs_diff_eq!(
+                face.pupils.1,
+                *face.landmarks.right_eye().point(),
+                epsilon = 1.0
+            );
+        }
+
+        pipeline.shape = Some(ShapePerturbate::new(5));
+
+        for face in pipeline.run(&mut rng, &image).iter() {
+            assert_eq!(face.landmarks_spread.as_ref().map(Vec::len), Some(5));
+        }
+    }
+
+    #[test]
+    fn test_face_pipeline_unknown_layout() {
+        let result = FacePipeline::builder()
+            .detector(detector())
+            .shaper(shaper(&[0.2, 0.3, 0.8, 0.3, 0.5, 0.6]))
+            .localizer(localizer())
+            .detect(detect())
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(FacePipelineBuilderError::ValidationError(_))
+        ));
+    }
+}
@@ -0,0 +1,52 @@
+use nalgebra::{Point2, Point3};
+
+/// Simple pinhole camera with square pixels and no distortion.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    pub(crate) focal_length: f32,
+    pub(crate) center: Point2<f32>,
+}
+
+impl Camera {
+    /// Creates a new camera with the specified focal length
+    /// and principal point, both in pixels.
+    #[inline]
+    pub fn new(focal_length: f32, cx: f32, cy: f32) -> Self {
+        Self {
+            focal_length,
+            center: Point2::new(cx, cy),
+        }
+    }
+
+    /// Creates a camera approximation for the image size with the focal length
+    /// equal to the image width and the principal point in the image center.
+    #[inline]
+    pub fn from_image_size(width: u32, height: u32) -> Self {
+        Self::new(width as f32, width as f32 / 2.0, height as f32 / 2.0)
+    }
+
+    /// Sets the focal length in pixels.
+    #[inline]
+    pub fn focal_length(self, value: f32) -> Self {
+        Self {
+            focal_length: value,
+            ..self
+        }
+    }
+
+    /// Returns the principal point of the camera.
+    #[inline]
+    pub fn center(&self) -> &Point2<f32> {
+        &self.center
+    }
+
+    /// Projects a point in camera coordinates onto the image plane.
+    #[inline]
+    pub fn project(&self, point: &Point3<f32>) -> Point2<f32> {
+        let scale = self.focal_length / point.z;
+        Point2::new(
+            point.x.mul_add(scale, self.center.x),
+            point.y.mul_add(scale, self.center.y),
+        )
+    }
+}
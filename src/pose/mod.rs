@@ -0,0 +1,319 @@
+mod camera;
+mod template;
+
+use nalgebra::{Matrix2x3, Matrix3, Matrix6, Point2, Point3, Rotation3, Vector3, Vector6};
+use thiserror::Error;
+
+pub use camera::Camera;
+pub use template::FaceTemplate;
+
+/// Head pose relative to the camera.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeadPose {
+    rotation: Rotation3<f32>,
+    translation: Vector3<f32>,
+    error: f32,
+}
+
+impl HeadPose {
+    /// Rotation from the template frame to the camera frame.
+    #[inline]
+    pub fn rotation(&self) -> &Rotation3<f32> {
+        &self.rotation
+    }
+
+    /// Translation from the template frame to the camera frame
+    /// in the template units.
+    #[inline]
+    pub fn translation(&self) -> &Vector3<f32> {
+        &self.translation
+    }
+
+    /// Root mean square distance in pixels between the landmarks
+    /// and the projected template points.
+    #[inline]
+    pub fn reprojection_error(&self) -> f32 {
+        self.error
+    }
+
+    /// Rotation around the vertical axis in radians,
+    /// positive when the face turns to the image left.
+    #[inline]
+    pub fn yaw(&self) -> f32 {
+        self.rotation.euler_angles().1
+    }
+
+    /// Rotation around the horizontal axis in radians,
+    /// positive when the face turns down.
+    #[inline]
+    pub fn pitch(&self) -> f32 {
+        self.rotation.euler_angles().0
+    }
+
+    /// Rotation around the optical axis in radians,
+    /// positive when the face tilts clockwise in the image.
+    #[inline]
+    pub fn roll(&self) -> f32 {
+        self.rotation.euler_angles().2
+    }
+
+    /// Project a point in the template frame onto the image.
+    #[inline]
+    pub fn project(&self, camera: &Camera, point: &Point3<f32>) -> Point2<f32> {
+        camera.project(&(self.rotation * point + self.translation))
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum HeadPoseError {
+    #[error("template needs at least {expected} landmarks, got {actual}")]
+    ShapeTooSmall { expected: usize, actual: usize },
+    #[error("template should have at least 4 points")]
+    NotEnoughPoints,
+    #[error("pose estimation diverged")]
+    Diverged,
+}
+
+/// Head pose estimation from shaper landmarks by solving
+/// the Perspective-n-Point problem with a 3D face template.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadPoseEstimator {
+    /// 3D face template matching the landmarks layout.
+    pub template: FaceTemplate,
+    /// Camera used to capture the image.
+    pub camera: Camera,
+    /// Maximum number of Levenberg-Marquardt iterations.
+    pub iterations: usize,
+}
+
+impl HeadPoseEstimator {
+    /// Creates a new estimator with 30 iterations at most.
+    #[inline]
+    pub fn new(template: FaceTemplate, camera: Camera) -> Self {
+        Self {
+            template,
+            camera,
+            iterations: 30,
+        }
+    }
+
+    /// Estimate head pose from the landmarks found by [`crate::Shaper`].
+    #[inline]
+    pub fn estimate(&self, shape: &[Point2<f32>]) -> Result<HeadPose, HeadPoseError> {
+        let expected = self.template.shape_size();
+        if shape.len() < expected {
+            return Err(HeadPoseError::ShapeTooSmall {
+                expected,
+                actual: shape.len(),
+            });
+        }
+
+        let pairs: Vec<(Point3<f32>, Point2<f32>)> = self
+            .template
+            .points()
+            .iter()
+            .map(|(index, point)| (*point, shape[*index]))
+            .collect();
+
+        if pairs.len() < 4 {
+            return Err(HeadPoseError::NotEnoughPoints);
+        }
+
+        let mut rotation = Rotation3::identity();
+        let mut translation = self.initial_translation(&pairs);
+        let mut error = self.squared_error(&pairs, &rotation, &translation);
+        let mut damping = 1e-3f32;
+
+        for _ in 0..self.iterations {
+            let mut jtj = Matrix6::<f32>::zeros();
+            let mut jtr = Vector6::<f32>::zeros();
+
+            for (model, image) in pairs.iter() {
+                let rotated = rotation * model;
+                let point = rotated + translation;
+                let residual = self.camera.project(&point) - image;
+
+                let (f, z) = (self.camera.focal_length, point.z);
+                let projection = Matrix2x3::new(
+                    f / z,
+                    0.0,
+                    -f * point.x / (z * z),
+                    0.0,
+                    f / z,
+                    -f * point.y / (z * z),
+                );
+
+                let mut jacobian = nalgebra::Matrix2x6::<f32>::zeros();
+                jacobian
+                    .fixed_view_mut::<2, 3>(0, 0)
+                    .copy_from(&(projection * -rotated.coords.cross_matrix()));
+                jacobian
+                    .fixed_view_mut::<2, 3>(0, 3)
+                    .copy_from(&(projection * Matrix3::identity()));
+
+                jtj += jacobian.transpose() * jacobian;
+                jtr += jacobian.transpose() * residual;
+            }
+
+            let mut accepted = false;
+
+            while damping < 1e10 {
+                let mut system = jtj;
+                for i in 0..6 {
+                    system[(i, i)] += damping * jtj[(i, i)].max(f32::EPSILON);
+                }
+
+                let Some(step) = system.lu().solve(&-jtr) else {
+                    damping *= 10.0;
+                    continue;
+                };
+
+                let rotation_step = Rotation3::new(step.fixed_rows::<3>(0).into_owned());
+                let new_rotation = rotation_step * rotation;
+                let new_translation = translation + step.fixed_rows::<3>(3);
+                let new_error = self.squared_error(&pairs, &new_rotation, &new_translation);
+
+                if new_error < error {
+                    let converged = error - new_error <= error * 1e-7;
+
+                    rotation = new_rotation;
+                    translation = new_translation;
+                    error = new_error;
+                    damping = (damping / 10.0).max(1e-7);
+                    accepted = !converged;
+                    break;
+                }
+
+                damping *= 10.0;
+            }
+
+            if !accepted {
+                break;
+            }
+        }
+
+        if !error.is_finite() || translation.z <= 0.0 {
+            return Err(HeadPoseError::Diverged);
+        }
+
+        Ok(HeadPose {
+            rotation,
+            translation,
+            error: (error / pairs.len() as f32).sqrt(),
+        })
+    }
+
+    /// Guess translation of the frontal face from the landmarks spread.
+    #[inline]
+    fn initial_translation(&self, pairs: &[(Point3<f32>, Point2<f32>)]) -> Vector3<f32> {
+        let scale = (pairs.len() as f32).recip();
+
+        let model_center: Vector3<f32> =
+            pairs.iter().map(|(m, _)| m.coords).sum::<Vector3<f32>>() * scale;
+        let image_center: nalgebra::Vector2<f32> = pairs
+            .iter()
+            .map(|(_, i)| i.coords)
+            .sum::<nalgebra::Vector2<f32>>()
+            * scale;
+
+        let model_spread: f32 = pairs
+            .iter()
+            .map(|(m, _)| (m.coords - model_center).xy().norm())
+            .sum();
+        let image_spread: f32 = pairs
+            .iter()
+            .map(|(_, i)| (i.coords - image_center).norm())
+            .sum();
+
+        let z = self.camera.focal_length * model_spread / image_spread.max(f32::EPSILON);
+        let xy = (image_center - self.camera.center.coords) * (z / self.camera.focal_length);
+
+        Vector3::new(xy.x, xy.y, z) - model_center
+    }
+
+    #[inline]
+    fn squared_error(
+        &self,
+        pairs: &[(Point3<f32>, Point2<f32>)],
+        rotation: &Rotation3<f32>,
+        translation: &Vector3<f32>,
+    ) -> f32 {
+        pairs
+            .iter()
+            .map(|(model, image)| {
+                let point = rotation * model + translation;
+                if point.z <= 0.0 {
+                    f32::INFINITY
+                } else {
+                    (self.camera.project(&point) - image).norm_squared()
+                }
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project_template(
+        template: &FaceTemplate,
+        camera: &Camera,
+        pose: &HeadPose,
+    ) -> Vec<Point2<f32>> {
+        let mut shape = vec![Point2::origin(); template.shape_size()];
+        for (index, point) in template.points() {
+            shape[*index] = pose.project(camera, point);
+        }
+        shape
+    }
+
+    fn check_estimate(template: FaceTemplate) {
+        let camera = Camera::from_image_size(640, 480);
+        let (yaw, pitch, roll) = (0.35f32, -0.2f32, 0.1f32);
+
+        let pose = HeadPose {
+            rotation: Rotation3::from_euler_angles(pitch, yaw, roll),
+            translation: Vector3::new(150.0, -80.0, 3000.0),
+            error: 0.0,
+        };
+
+        assert_abs_diff_eq!(pose.yaw(), yaw, epsilon = 1e-5);
+        assert_abs_diff_eq!(pose.pitch(), pitch, epsilon = 1e-5);
+        assert_abs_diff_eq!(pose.roll(), roll, epsilon = 1e-5);
+
+        let shape = project_template(&template, &camera, &pose);
+        let estimated = HeadPoseEstimator::new(template, camera)
+            .estimate(&shape)
+            .unwrap();
+
+        assert_abs_diff_eq!(estimated.yaw(), yaw, epsilon = 1e-2);
+        assert_abs_diff_eq!(estimated.pitch(), pitch, epsilon = 1e-2);
+        assert_abs_diff_eq!(estimated.roll(), roll, epsilon = 1e-2);
+        assert!(estimated.reprojection_error() < 0.1);
+    }
+
+    #[test]
+    fn test_head_pose_estimate_face5() {
+        check_estimate(FaceTemplate::face5());
+    }
+
+    #[test]
+    fn test_head_pose_estimate_face68() {
+        check_estimate(FaceTemplate::face68());
+    }
+
+    #[test]
+    fn test_head_pose_estimate_small_shape() {
+        let estimator =
+            HeadPoseEstimator::new(FaceTemplate::face68(), Camera::from_image_size(640, 480));
+
+        assert_eq!(
+            estimator.estimate(&[Point2::origin(); 5]),
+            Err(HeadPoseError::ShapeTooSmall {
+                expected: 55,
+                actual: 5
+            })
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use nalgebra::Point3;
+
+use crate::landmarks::{face5, face68};
+
+/// Generic 3D face model matching some landmarks of a shaper model.
+///
+/// Coordinates are in arbitrary units with `x` axis pointing to the image right,
+/// `y` axis pointing down and `z` axis pointing away from the camera
+/// for a frontal face. Rotation of the estimated pose is relative to this frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FaceTemplate {
+    points: Vec<(usize, Point3<f32>)>,
+}
+
+impl FaceTemplate {
+    /// Creates a template from pairs of a landmark index and its 3D position.
+    #[inline]
+    pub fn new(points: Vec<(usize, Point3<f32>)>) -> Self {
+        Self { points }
+    }
+
+    /// Template for the 5 face landmarks model (`face-5.shaper.bin`).
+    #[inline]
+    pub fn face5() -> Self {
+        Self::new(vec![
+            (face5::LEFT_EYE_OUTER, Point3::new(225.0, -170.0, 135.0)),
+            (face5::LEFT_EYE_INNER, Point3::new(75.0, -165.0, 110.0)),
+            (face5::RIGHT_EYE_OUTER, Point3::new(-225.0, -170.0, 135.0)),
+            (face5::RIGHT_EYE_INNER, Point3::new(-75.0, -165.0, 110.0)),
+            (face5::NOSE_BASE, Point3::new(0.0, 55.0, 75.0)),
+        ])
+    }
+
+    /// Template for the 68 face landmarks model in the iBUG 300-W layout.
+    #[inline]
+    pub fn face68() -> Self {
+        Self::new(vec![
+            (face68::CHIN, Point3::new(0.0, 330.0, 65.0)),
+            (face68::NOSE_TIP, Point3::new(0.0, 0.0, 0.0)),
+            (face68::RIGHT_EYE_OUTER, Point3::new(-225.0, -170.0, 135.0)),
+            (face68::RIGHT_EYE_INNER, Point3::new(-75.0, -165.0, 110.0)),
+            (face68::LEFT_EYE_INNER, Point3::new(75.0, -165.0, 110.0)),
+            (face68::LEFT_EYE_OUTER, Point3::new(225.0, -170.0, 135.0)),
+            (face68::MOUTH_RIGHT, Point3::new(-150.0, 150.0, 125.0)),
+            (face68::MOUTH_LEFT, Point3::new(150.0, 150.0, 125.0)),
+        ])
+    }
+
+    /// Returns pairs of a landmark index and its 3D position.
+    #[inline]
+    pub fn points(&self) -> &[(usize, Point3<f32>)] {
+        &self.points
+    }
+
+    /// Returns the minimal shape size the template can be applied to.
+    #[inline]
+    pub fn shape_size(&self) -> usize {
+        self.points
+            .iter()
+            .map(|(index, _)| index + 1)
+            .max()
+            .unwrap_or(0)
+    }
+}
@@ -1,13 +1,15 @@
+use alloc::vec::Vec;
 use core::mem::size_of;
-use std::io::{Error, Read};
 
-use image::Luma;
 use nalgebra::{Affine2, Point2, SimilarityMatrix2};
-use pixelutil_image::ExtendedImageView;
 
 use super::delta::ShaperDelta;
+use super::sampling::Sampler;
 use super::tree::ShaperTree;
 
+use crate::io::{Error, Read};
+use crate::traits::GrayImageView;
+
 #[derive(Debug, Clone)]
 pub struct ShaperForest {
     deltas: Vec<ShaperDelta>,
@@ -35,6 +37,7 @@ impl ShaperForest {
         &self.trees
     }
 
+    /// Returns `None` if any feature is rejected by the border mode.
     #[inline]
     pub(super) fn extract_features<I>(
         &self,
@@ -42,9 +45,10 @@ impl ShaperForest {
         transform_to_shape: &SimilarityMatrix2<f32>,
         transform_to_image: &Affine2<f32>,
         shape: &[Point2<f32>],
-    ) -> Vec<u8>
+        sampler: Sampler,
+    ) -> Option<Vec<u8>>
     where
-        I: ExtendedImageView<Pixel = Luma<u8>>,
+        I: GrayImageView,
     {
         self.deltas
             .iter()
@@ -52,13 +56,8 @@ impl ShaperForest {
                 let point = unsafe { shape.get_unchecked(delta.anchor()) };
                 let point = point + transform_to_shape.transform_vector(delta.value());
                 let point = transform_to_image * point;
-                let point =
-                    Point2::from(unsafe { point.coords.try_cast::<i32>().unwrap_unchecked() });
 
-                image
-                    .get_pixel_at(point)
-                    .map(|Luma([value])| value)
-                    .unwrap_or(0u8)
+                sampler.sample(image, point)
             })
             .collect()
     }
@@ -121,3 +120,56 @@ impl ShaperForest {
         Ok(Self { trees, deltas })
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use image::{GrayImage, Luma};
+    use nalgebra::Matrix3;
+
+    use super::super::sampling::Interpolation;
+    use super::*;
+
+    #[test]
+    fn test_extract_features_subpixel_stability() {
+        let image = GrayImage::from_fn(16, 16, |x, _| Luma([(x * 12) as u8]));
+
+        let forest = ShaperForest {
+            deltas: vec![ShaperDelta::new(0, 0.0, 0.0)],
+            trees: Vec::new(),
+        };
+
+        let transform_to_shape = SimilarityMatrix2::identity();
+        let transform_to_image = Affine2::from_matrix_unchecked(Matrix3::new(
+            10.0, 0.0, 0.0, 0.0, 10.0, 0.0, 0.0, 0.0, 1.0,
+        ));
+
+        let extract = |x: f32, interpolation| {
+            forest
+                .extract_features(
+                    &image,
+                    &transform_to_shape,
+                    &transform_to_image,
+                    &[Point2::new(x, 1.0)],
+                    Sampler {
+                        interpolation,
+                        ..Default::default()
+                    },
+                )
+                .unwrap()[0]
+        };
+
+        // shape point moves by a tenth of a pixel on each step
+        let nearest: Vec<u8> = (0..10)
+            .map(|i| extract(1.0 + i as f32 / 100.0, Interpolation::Nearest))
+            .collect();
+        let bilinear: Vec<u8> = (0..10)
+            .map(|i| extract(1.0 + i as f32 / 100.0, Interpolation::Bilinear))
+            .collect();
+
+        assert!(nearest.iter().all(|value| *value == nearest[0]));
+
+        for (i, pair) in bilinear.windows(2).enumerate() {
+            assert!(pair[0] < pair[1], "step {} is not increasing", i);
+        }
+    }
+}
@@ -1,19 +1,26 @@
 mod delta;
 mod forest;
+mod sampling;
 mod tree;
 mod utils;
 
-use std::{
-    fmt::Debug,
-    io::{Error, ErrorKind, Read},
-};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Debug;
 
-use image::Luma;
-use imageproc::rect::Rect;
-use nalgebra::{Affine2, DimName, Matrix3, Point2, SimilarityMatrix2, U2};
+use nalgebra::{Affine2, DimName, Dyn, Matrix3, OMatrix, Point2, SimilarityMatrix2, U2};
+use rand::RngCore;
 
 use forest::ShaperForest;
-use pixelutil_image::ExtendedImageView;
+use sampling::Sampler;
+
+pub use sampling::{Interpolation, Normalization};
+
+use crate::border::BorderMode;
+use crate::geometry::{Rectangle, Target};
+use crate::io::{Error, ErrorKind, Read};
+use crate::localize::perturbate::{HaltonPerturbator, Perturbator};
+use crate::traits::GrayImageView;
 
 /// Implements object alignment using an ensemble of regression trees.
 #[derive(Clone)]
@@ -22,15 +29,21 @@ pub struct Shaper {
     dsize: usize,
     shape: Vec<Point2<f32>>,
     forests: Vec<ShaperForest>,
+    interpolation: Interpolation,
+    border: BorderMode,
+    normalization: Normalization,
 }
 
 impl Debug for Shaper {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct(stringify!(Shaper))
             .field("depth", &self.depth)
             .field("dsize", &self.dsize)
             .field("shape", &self.shape.len())
             .field("forests", &self.forests.len())
+            .field("interpolation", &self.interpolation)
+            .field("border", &self.border)
+            .field("normalization", &self.normalization)
             .finish()
     }
 }
@@ -46,6 +59,58 @@ impl Shaper {
         self.shape.as_ref()
     }
 
+    /// Returns the number of forests in the cascade.
+    #[inline]
+    pub fn forests(&self) -> usize {
+        self.forests.len()
+    }
+
+    /// Returns the pixel interpolation used to extract features.
+    #[inline]
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Set the pixel interpolation used to extract features.
+    ///
+    /// [`Interpolation::Bilinear`] makes landmarks stable to sub-pixel
+    /// changes of the region, which matters for small objects.
+    #[inline]
+    pub fn set_interpolation(&mut self, value: Interpolation) {
+        self.interpolation = value;
+    }
+
+    /// Returns the handling of features falling outside of the image.
+    #[inline]
+    pub fn border(&self) -> BorderMode {
+        self.border
+    }
+
+    /// Set the handling of features falling outside of the image.
+    ///
+    /// [`BorderMode::ZERO`] is used by default. With [`BorderMode::Reject`]
+    /// refinement stops at the first forest with a feature outside of the image.
+    #[inline]
+    pub fn set_border(&mut self, value: BorderMode) {
+        self.border = value;
+    }
+
+    /// Returns the mapping of image luminance to 8-bit features.
+    #[inline]
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+
+    /// Set the mapping of image luminance to 8-bit features.
+    ///
+    /// [`Normalization::FullRange`] is used by default, while
+    /// [`Normalization::range`] suits images with a narrow range of values,
+    /// e.g. 12-bit data stored as `u16`.
+    #[inline]
+    pub fn set_normalization(&mut self, value: Normalization) {
+        self.normalization = value;
+    }
+
     /// Create a shaper object from a readable source.
     #[inline]
     pub fn load<R: Read>(mut reader: R) -> Result<Self, Error> {
@@ -81,8 +146,8 @@ impl Shaper {
 
         // dbg!(nrows, ncols, nforests, forest_size, tree_depth, nfeatures);
         let shape: Vec<Point2<f32>> = utils::read_shape(reader.by_ref(), shape_size)?
-            .column_iter()
-            .map(|col| Point2::new(col.x, col.y))
+            .into_iter()
+            .map(Point2::from)
             .collect();
 
         let mut forests = Vec::with_capacity(nforests);
@@ -102,6 +167,9 @@ impl Shaper {
             dsize: nodes_count,
             shape,
             forests,
+            interpolation: Default::default(),
+            border: BorderMode::ZERO,
+            normalization: Default::default(),
         })
     }
 
@@ -111,25 +179,96 @@ impl Shaper {
     /// ### Arguments
     ///
     /// * `image` - Target image.
+    /// * `rect` - Object region defining the normalized shape space,
+    ///   e.g. [`Rectangle`], [`Target`] or `imageproc` rectangle.
     ///
     /// ### Returns
     ///
     /// A collection of points each one corresponds to landmark location.
     /// Points count is defined by a loaded shaper model.
     #[inline]
-    pub fn shape<I>(&self, image: &I, rect: Rect) -> Vec<Point2<f32>>
+    pub fn shape<I, R>(&self, image: &I, rect: R) -> Vec<Point2<f32>>
     where
-        I: ExtendedImageView<Pixel = Luma<u8>>,
+        I: GrayImageView,
+        R: Into<Rectangle>,
     {
-        let mut shape = self.shape.clone();
+        let transform_to_image = find_transform_to_image(rect.into());
 
-        let transform_to_image = find_transform_to_image(rect);
+        self.run(
+            image,
+            &transform_to_image,
+            self.shape.clone(),
+            self.forests.as_slice(),
+        )
+    }
 
-        for forest in self.forests.iter() {
+    /// Refine object shape on the image starting from the initial shape
+    /// instead of the mean one, e.g. landmarks found on the previous video frame.
+    ///
+    /// ### Arguments
+    ///
+    /// * `image` - Target image.
+    /// * `rect` - Object region defining the normalized shape space.
+    /// * `init` - Initial shape in image coordinates.
+    /// * `forests` - Number of the last forests from the cascade to run;
+    ///   running fewer forests is cheaper, see [`Shaper::forests`].
+    ///
+    /// ### Panics
+    ///
+    /// If the initial shape size is not equal to [`Shaper::size`].
+    #[inline]
+    pub fn refine<I, R>(
+        &self,
+        image: &I,
+        rect: R,
+        init: &[Point2<f32>],
+        forests: usize,
+    ) -> Vec<Point2<f32>>
+    where
+        I: GrayImageView,
+        R: Into<Rectangle>,
+    {
+        assert_eq!(init.len(), self.size());
+
+        let transform_to_image = find_transform_to_image(rect.into());
+        let transform_to_rect = transform_to_image.inverse();
+
+        let shape = init.iter().map(|point| transform_to_rect * point).collect();
+        let skip = self.forests.len().saturating_sub(forests);
+
+        self.run(image, &transform_to_image, shape, &self.forests[skip..])
+    }
+
+    #[inline]
+    fn run<I>(
+        &self,
+        image: &I,
+        transform_to_image: &Affine2<f32>,
+        mut shape: Vec<Point2<f32>>,
+        forests: &[ShaperForest],
+    ) -> Vec<Point2<f32>>
+    where
+        I: GrayImageView,
+    {
+        let sampler = Sampler {
+            interpolation: self.interpolation,
+            border: self.border,
+            normalization: self.normalization,
+        };
+
+        for forest in forests.iter() {
             let transform_to_shape = Self::find_transform(self, shape.as_slice());
 
-            let features =
-                forest.extract_features(image, &transform_to_shape, &transform_to_image, &shape);
+            let features = match forest.extract_features(
+                image,
+                &transform_to_shape,
+                transform_to_image,
+                &shape,
+                sampler,
+            ) {
+                Some(features) => features,
+                None => break,
+            };
 
             for tree in forest.trees_slice().iter() {
                 let idx = (0..self.depth).fold(0, |idx, _| {
@@ -156,10 +295,17 @@ impl Shaper {
 
     #[inline]
     fn find_transform(&self, shape: &[Point2<f32>]) -> SimilarityMatrix2<f32> {
+        let matrix = |points: &[Point2<f32>]| {
+            OMatrix::<f32, U2, Dyn>::from_iterator(
+                points.len(),
+                points.iter().flat_map(|point| point.coords.iter().copied()),
+            )
+        };
+
         unsafe {
-            similarity_least_squares::from_point_slices(
-                self.shape.as_slice(),
-                shape,
+            similarity_least_squares::from_matrices(
+                matrix(self.shape.as_slice()),
+                matrix(shape),
                 f32::EPSILON,
                 0,
             )
@@ -168,15 +314,179 @@ impl Shaper {
     }
 }
 
+/// Implements object alignment with perturbation.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapePerturbate {
+    /// Perturbator to apply to the target.
+    pub perturbator: Perturbator,
+    /// Number of perturbations to run.
+    pub runs: usize,
+}
+
+impl Default for ShapePerturbate {
+    /// Creates with default perturbator and runs count set to 15.
+    #[inline]
+    fn default() -> Self {
+        Self {
+            perturbator: Default::default(),
+            runs: 15,
+        }
+    }
+}
+
+impl ShapePerturbate {
+    /// Creates a new instance with the specified number of runs
+    /// with a default perturbator.
+    #[inline]
+    pub fn new(runs: usize) -> Self {
+        Self {
+            perturbator: Default::default(),
+            runs,
+        }
+    }
+
+    /// Applies perturbations to the target and runs the shaper on each perturbed target.
+    ///
+    /// ### Returns
+    ///
+    /// Per landmark median of the estimated shapes. With zero `runs`
+    /// the shaper runs once on the target itself.
+    #[inline]
+    pub fn run<R, I>(
+        &self,
+        shaper: &Shaper,
+        rng: &mut R,
+        image: &I,
+        target: Target,
+    ) -> Vec<Point2<f32>>
+    where
+        R: RngCore,
+        I: GrayImageView,
+    {
+        self.run_with_spread(shaper, rng, image, target).0
+    }
+
+    /// Same as [`ShapePerturbate::run`], but also returns the spread of each landmark.
+    ///
+    /// The spread is the median distance between the landmark estimated on
+    /// the perturbed targets and the resulting landmark. The lower the spread,
+    /// the more confident is the estimation. With zero `runs` the spread is zero.
+    #[inline]
+    pub fn run_with_spread<R, I>(
+        &self,
+        shaper: &Shaper,
+        rng: &mut R,
+        image: &I,
+        target: Target,
+    ) -> (Vec<Point2<f32>>, Vec<f32>)
+    where
+        R: RngCore,
+        I: GrayImageView,
+    {
+        self.shape(shaper, image, target, |f| {
+            self.perturbator.run(rng, self.runs, target, f)
+        })
+    }
+
+    /// Applies deterministic perturbations from the `perturbator` to the target
+    /// and runs the shaper on each perturbed target.
+    #[inline]
+    pub fn run_halton<I>(
+        &self,
+        shaper: &Shaper,
+        perturbator: &HaltonPerturbator,
+        image: &I,
+        target: Target,
+    ) -> Vec<Point2<f32>>
+    where
+        I: GrayImageView,
+    {
+        self.run_halton_with_spread(shaper, perturbator, image, target)
+            .0
+    }
+
+    /// Same as [`ShapePerturbate::run_halton`], but also returns the spread of each landmark.
+    /// See [`ShapePerturbate::run_with_spread`] for more details.
+    #[inline]
+    pub fn run_halton_with_spread<I>(
+        &self,
+        shaper: &Shaper,
+        perturbator: &HaltonPerturbator,
+        image: &I,
+        target: Target,
+    ) -> (Vec<Point2<f32>>, Vec<f32>)
+    where
+        I: GrayImageView,
+    {
+        self.shape(shaper, image, target, |f| {
+            perturbator.run(self.runs, target, f)
+        })
+    }
+
+    #[inline]
+    fn shape<I, P>(
+        &self,
+        shaper: &Shaper,
+        image: &I,
+        target: Target,
+        perturbate: P,
+    ) -> (Vec<Point2<f32>>, Vec<f32>)
+    where
+        I: GrayImageView,
+        P: FnOnce(&mut dyn FnMut(Target)),
+    {
+        let mut shapes: Vec<Vec<Point2<f32>>> = Vec::with_capacity(self.runs);
+
+        perturbate(&mut |t| shapes.push(shaper.shape(image, t)));
+
+        if shapes.is_empty() {
+            return (shaper.shape(image, target), vec![0.0; shaper.size()]);
+        }
+
+        let mut xs: Vec<f32> = Vec::with_capacity(shapes.len());
+        let mut ys: Vec<f32> = Vec::with_capacity(shapes.len());
+        let mut ds: Vec<f32> = Vec::with_capacity(shapes.len());
+
+        let index = (shapes.len() - 1) / 2;
+
+        (0..shaper.size())
+            .map(|i| {
+                xs.clear();
+                ys.clear();
+                ds.clear();
+
+                for shape in shapes.iter() {
+                    xs.push(shape[i].x);
+                    ys.push(shape[i].y);
+                }
+
+                xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                ys.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+                let point = Point2::new(xs[index], ys[index]);
+
+                ds.extend(
+                    shapes
+                        .iter()
+                        .map(|shape| nalgebra::distance(&shape[i], &point)),
+                );
+                ds.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+                (point, ds[index])
+            })
+            .unzip()
+    }
+}
+
 #[inline]
-fn find_transform_to_image(rect: Rect) -> Affine2<f32> {
+fn find_transform_to_image(rect: Rectangle) -> Affine2<f32> {
     Affine2::from_matrix_unchecked(Matrix3::new(
-        rect.width() as f32,
+        rect.width as f32,
         0.0,
-        rect.left() as f32,
+        rect.left as f32,
         0.0,
-        rect.height() as f32,
-        rect.top() as f32,
+        rect.height as f32,
+        rect.top as f32,
         0.0,
         0.0,
         1.0,
@@ -185,8 +495,248 @@ fn find_transform_to_image(rect: Rect) -> Affine2<f32> {
 
 #[cfg(test)]
 mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_xoshiro::Xoroshiro128PlusPlus;
+
+    use crate::view::GrayView;
+
     use super::*;
 
+    fn mean_shaper() -> Shaper {
+        Shaper {
+            depth: 0,
+            dsize: 0,
+            shape: vec![
+                Point2::new(0.25, 0.25),
+                Point2::new(0.75, 0.25),
+                Point2::new(0.5, 0.75),
+            ],
+            forests: Vec::new(),
+            interpolation: Default::default(),
+            border: BorderMode::ZERO,
+            normalization: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_shaper_refine_keeps_init_shape() {
+        let shaper = mean_shaper();
+        let data = vec![0u8; 200 * 200];
+        let image = GrayView::packed(&data, 200, 200).unwrap();
+        let rect = Rectangle::new(40, 60, 100, 80);
+
+        let init = vec![
+            Point2::new(70.0, 80.0),
+            Point2::new(110.0, 82.0),
+            Point2::new(92.0, 120.0),
+        ];
+
+        for (p1, p2) in shaper
+            .refine(&image, rect, &init, shaper.forests())
+            .iter()
+            .zip(init.iter())
+        {
+            assert_abs_diff_eq!(*p1, *p2, epsilon = 1e-4);
+        }
+    }
+
+    /// Cascade of `nforests` forests of one stump each, comparing features
+    /// left and right of the first point, every forest shifts the shape differently.
+    fn cascade_shaper(nforests: u32) -> Shaper {
+        let be = |values: &[f32]| -> Vec<u8> {
+            values
+                .iter()
+                .flat_map(|value| value.to_be_bytes())
+                .collect()
+        };
+
+        let mut data = vec![1u8];
+        for value in [2u32, 3, nforests, 1, 1, 2] {
+            data.extend(value.to_be_bytes());
+        }
+        data.extend(be(&[0.25, 0.25, 0.75, 0.25, 0.5, 0.75]));
+
+        for i in 0..nforests {
+            let shift = 0.02 * (i + 1) as f32;
+
+            data.extend(0u32.to_be_bytes());
+            data.extend(1u32.to_be_bytes());
+            data.extend(0i16.to_be_bytes());
+            data.extend(be(&[shift, -0.01, 0.0, shift, -shift, 0.0]));
+            data.extend(be(&[-0.01, shift, shift, 0.0, 0.0, -shift]));
+
+            data.extend(0u32.to_be_bytes());
+            data.extend(0u32.to_be_bytes());
+            data.extend(be(&[-0.1, 0.0, 0.1, 0.0]));
+        }
+
+        Shaper::load(data.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_shaper_refine_last_forests() {
+        let shaper = cascade_shaper(4);
+        assert_eq!(shaper.forests(), 4);
+
+        let mut rng = Xoroshiro128PlusPlus::seed_from_u64(42);
+        let data: Vec<u8> = (0..200 * 200).map(|_| rng.random()).collect();
+        let image = GrayView::packed(&data, 200, 200).unwrap();
+        let rect = Rectangle::new(40, 60, 100, 80);
+
+        let init = vec![
+            Point2::new(70.0, 80.0),
+            Point2::new(110.0, 82.0),
+            Point2::new(92.0, 120.0),
+        ];
+
+        let mut results = Vec::new();
+
+        for k in 0..=shaper.forests() {
+            let last = Shaper {
+                forests: shaper.forests[shaper.forests() - k..].to_vec(),
+                ..shaper.clone()
+            };
+            let expected = last.refine(&image, rect, &init, last.forests());
+            let points = shaper.refine(&image, rect, &init, k);
+
+            for (p1, p2) in points.iter().zip(expected.iter()) {
+                assert_abs_diff_eq!(*p1, *p2, epsilon = 1e-4);
+            }
+
+            results.push(points);
+        }
+
+        // each forest moves the shape
+        for (p1, p2) in results.iter().zip(results.iter().skip(1)) {
+            assert!(p1
+                .iter()
+                .zip(p2.iter())
+                .any(|(p1, p2)| (p1 - p2).norm() > 0.5));
+        }
+
+        // the initial shape is kept without forests
+        for (p1, p2) in results[0].iter().zip(init.iter()) {
+            assert_abs_diff_eq!(*p1, *p2, epsilon = 1e-4);
+        }
+
+        // more forests than in the cascade run the whole cascade
+        let all = shaper.refine(&image, rect, &init, shaper.forests());
+        for k in [shaper.forests() + 1, 100, usize::MAX] {
+            assert_eq!(shaper.refine(&image, rect, &init, k), all);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_shaper_refine_wrong_init_size() {
+        let shaper = mean_shaper();
+        let data = vec![0u8; 200 * 200];
+        let image = GrayView::packed(&data, 200, 200).unwrap();
+        let rect = Rectangle::new(40, 60, 100, 80);
+
+        shaper.refine(&image, rect, &[Point2::new(70.0, 80.0)], 1);
+    }
+
+    #[test]
+    fn test_shape_perturbate_run_halton() {
+        let shaper = mean_shaper();
+        let data = vec![0u8; 200 * 200];
+        let image = GrayView::packed(&data, 200, 200).unwrap();
+        let target = Target::new(100.0, 100.0, 80.0);
+
+        let shape = shaper.shape(&image, target);
+
+        let (points, spread) = ShapePerturbate::new(31).run_halton_with_spread(
+            &shaper,
+            &HaltonPerturbator::default(),
+            &image,
+            target,
+        );
+
+        assert_eq!(points.len(), shaper.size());
+        assert_eq!(spread.len(), shaper.size());
+
+        for ((p1, p2), s) in points.iter().zip(shape.iter()).zip(spread.iter()) {
+            assert_abs_diff_eq!(*p1, *p2, epsilon = 5.0);
+            assert!(*s > 0.0 && *s < 10.0);
+        }
+    }
+
+    #[test]
+    fn test_shape_perturbate_zero_runs() {
+        let shaper = mean_shaper();
+        let data = vec![0u8; 200 * 200];
+        let image = GrayView::packed(&data, 200, 200).unwrap();
+        let target = Target::new(100.0, 100.0, 80.0);
+
+        let shape = shaper.shape(&image, target);
+        let perturbate = ShapePerturbate::new(0);
+
+        let (points, spread) = perturbate.run_with_spread(
+            &shaper,
+            &mut Xoroshiro128PlusPlus::seed_from_u64(42),
+            &image,
+            target,
+        );
+        assert_eq!(points, shape);
+        assert_eq!(spread, vec![0.0; shaper.size()]);
+
+        let points = perturbate.run_halton(&shaper, &HaltonPerturbator::default(), &image, target);
+        assert_eq!(points, shape);
+    }
+
+    #[test]
+    fn test_shaper_load_slice() {
+        let be = |values: &[f32]| -> Vec<u8> {
+            values
+                .iter()
+                .flat_map(|value| value.to_be_bytes())
+                .collect()
+        };
+
+        let mut data = vec![1u8];
+        // 2 x 3 shape, 1 forest of 1 tree with depth 1 and 2 features
+        for value in [2u32, 3, 1, 1, 1, 2] {
+            data.extend(value.to_be_bytes());
+        }
+        data.extend(be(&[0.25, 0.25, 0.75, 0.25, 0.5, 0.75]));
+
+        // node compares features 0 and 1 with zero threshold
+        data.extend(0u32.to_be_bytes());
+        data.extend(1u32.to_be_bytes());
+        data.extend(0i16.to_be_bytes());
+        data.extend(be(&[0.0; 6]));
+        data.extend(be(&[0.1, 0.0, 0.1, 0.0, 0.1, 0.0]));
+
+        // features to the left and to the right of the first point
+        data.extend(0u32.to_be_bytes());
+        data.extend(0u32.to_be_bytes());
+        data.extend(be(&[-0.1, 0.0, 0.1, 0.0]));
+
+        let shaper = Shaper::load(data.as_slice()).unwrap();
+        assert_eq!(shaper.size(), 3);
+        assert_eq!(shaper.forests(), 1);
+
+        assert_eq!(
+            Shaper::load(&data[..data.len() - 1]).unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+
+        // brighter to the right, so the shape is shifted by the second leaf
+        let pixels: Vec<u8> = (0..100 * 100).map(|i| (i % 100) as u8).collect();
+        let image = GrayView::packed(&pixels, 100, 100).unwrap();
+        let rect = Rectangle::new(10, 20, 80, 40);
+
+        for (point, init) in shaper
+            .shape(&image, rect)
+            .iter()
+            .zip(shaper.init_points().iter())
+        {
+            let expected = Point2::new(10.0 + 80.0 * (init.x + 0.1), 20.0 + 40.0 * init.y);
+            assert_abs_diff_eq!(*point, expected, epsilon = 1e-4);
+        }
+    }
+
     #[test]
     fn test_face_landmarks_model_loading() {
         let shaper = dbg!(Shaper::load(
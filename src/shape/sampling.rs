@@ -0,0 +1,312 @@
+use nalgebra::{ComplexField, Point2, Vector2};
+
+use crate::border::BorderMode;
+use crate::traits::{GrayImageView, Luminance};
+
+/// Pixel interpolation method used to extract shaper features.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    /// Take the pixel the point falls into.
+    #[default]
+    Nearest,
+    /// Interpolate between four pixels surrounding the point.
+    Bilinear,
+}
+
+/// Mapping of image luminance to 8-bit shaper features.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Normalization {
+    /// Scale the nominal range of the luminance type to `[0, 255]`:
+    /// `u8` is taken as is, `u16` is scaled from `[0, 65535]` and `f32` from `[0, 1]`.
+    #[default]
+    FullRange,
+    /// Scale `[min, max]` luminance range to `[0, 255]`, values outside are clamped.
+    ///
+    /// If `max` is not greater than `min`, values greater than `min` are mapped
+    /// to `255` and the rest to `0`, see [`Normalization::range`] for validation.
+    Range { min: f32, max: f32 },
+}
+
+impl Normalization {
+    /// Create [`Range`](Self::Range) normalization,
+    /// `None` unless both values are finite and `max` is greater than `min`.
+    #[inline]
+    pub fn range(min: f32, max: f32) -> Option<Self> {
+        if min.is_finite() && max.is_finite() && max > min {
+            Some(Self::Range { min, max })
+        } else {
+            None
+        }
+    }
+
+    /// Map the luminance value into `[0, 255]` range.
+    #[inline]
+    pub fn apply<T: Luminance>(self, value: T) -> f32 {
+        let value = match self {
+            Self::FullRange => value.to_f32() * (255.0 / T::MAX),
+            Self::Range { min, max } if max > min => (value.to_f32() - min) * (255.0 / (max - min)),
+            Self::Range { min, .. } => {
+                if value.to_f32() > min {
+                    255.0
+                } else {
+                    0.0
+                }
+            }
+        };
+        value.clamp(0.0, 255.0)
+    }
+}
+
+/// Shaper features sampling parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(super) struct Sampler {
+    pub interpolation: Interpolation,
+    pub border: BorderMode,
+    pub normalization: Normalization,
+}
+
+impl Sampler {
+    /// Returns `None` if the point is rejected by the border mode.
+    #[inline]
+    pub fn sample<I>(&self, image: &I, point: Point2<f32>) -> Option<u8>
+    where
+        I: GrayImageView,
+    {
+        sample(
+            image,
+            point,
+            self.interpolation,
+            self.border,
+            self.normalization,
+        )
+    }
+}
+
+/// Constant border value is taken as is, without normalization.
+#[inline]
+fn fetch<I>(
+    image: &I,
+    point: Point2<i32>,
+    border: BorderMode,
+    normalization: Normalization,
+) -> Option<f32>
+where
+    I: GrayImageView,
+{
+    match border {
+        BorderMode::Constant(value) => Some(
+            image
+                .luma_at(point)
+                .map_or(value as f32, |value| normalization.apply(value)),
+        ),
+        border => image
+            .luma_border(point, border)
+            .map(|value| normalization.apply(value)),
+    }
+}
+
+/// Sample image luminance at the point with sub-pixel coordinates,
+/// `None` if the point is rejected by the border mode.
+#[inline]
+fn sample<I>(
+    image: &I,
+    point: Point2<f32>,
+    interpolation: Interpolation,
+    border: BorderMode,
+    normalization: Normalization,
+) -> Option<u8>
+where
+    I: GrayImageView,
+{
+    match interpolation {
+        Interpolation::Nearest => {
+            let point = Point2::from(unsafe { point.coords.try_cast::<i32>().unwrap_unchecked() });
+            fetch(image, point, border, normalization).map(|value| ComplexField::round(value) as u8)
+        }
+        Interpolation::Bilinear => {
+            let origin = point.map(ComplexField::floor);
+            let (fx, fy) = (point.x - origin.x, point.y - origin.y);
+
+            let p00 = Point2::from(unsafe { origin.coords.try_cast::<i32>().unwrap_unchecked() });
+            let p10 = p00 + Vector2::x();
+            let p01 = p00 + Vector2::y();
+            let p11 = p10 + Vector2::y();
+
+            let v00 = fetch(image, p00, border, normalization)?;
+            let v10 = fetch(image, p10, border, normalization)?;
+            let v01 = fetch(image, p01, border, normalization)?;
+            let v11 = fetch(image, p11, border, normalization)?;
+
+            let top = ComplexField::mul_add(v10 - v00, fx, v00);
+            let bottom = ComplexField::mul_add(v11 - v01, fx, v01);
+
+            Some(ComplexField::round(ComplexField::mul_add(bottom - top, fy, top)) as u8)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use image::{GrayImage, ImageBuffer, Luma};
+
+    use super::*;
+
+    fn gradient() -> GrayImage {
+        GrayImage::from_fn(8, 8, |x, _| Luma([(x * 32) as u8]))
+    }
+
+    #[test]
+    fn test_sample_nearest() {
+        let image = gradient();
+
+        for (x, value) in [(2.0, 64), (2.4, 64), (2.9, 64), (3.0, 96)] {
+            let point = Point2::new(x, 4.0);
+            let sampled = sample(
+                &image,
+                point,
+                Interpolation::Nearest,
+                BorderMode::ZERO,
+                Default::default(),
+            );
+            assert_eq!(sampled, Some(value));
+        }
+    }
+
+    #[test]
+    fn test_sample_bilinear_subpixel() {
+        let image = gradient();
+
+        for (x, value) in [(2.0, 64), (2.25, 72), (2.5, 80), (2.75, 88), (3.0, 96)] {
+            let point = Point2::new(x, 4.5);
+            let sampled = sample(
+                &image,
+                point,
+                Interpolation::Bilinear,
+                BorderMode::Clamp,
+                Default::default(),
+            );
+            assert_eq!(sampled, Some(value));
+        }
+    }
+
+    #[test]
+    fn test_sample_border() {
+        let image = GrayImage::from_pixel(4, 4, Luma([200]));
+
+        for point in [Point2::new(-1.0, 1.0), Point2::new(1.0, 4.5)] {
+            for interpolation in [Interpolation::Nearest, Interpolation::Bilinear] {
+                assert_eq!(
+                    sample(
+                        &image,
+                        point,
+                        interpolation,
+                        BorderMode::ZERO,
+                        Default::default()
+                    ),
+                    Some(0)
+                );
+                assert_eq!(
+                    sample(
+                        &image,
+                        point,
+                        interpolation,
+                        BorderMode::Clamp,
+                        Default::default()
+                    ),
+                    Some(200)
+                );
+                assert_eq!(
+                    sample(
+                        &image,
+                        point,
+                        interpolation,
+                        BorderMode::Constant(42),
+                        Default::default()
+                    ),
+                    Some(42)
+                );
+                assert_eq!(
+                    sample(
+                        &image,
+                        point,
+                        interpolation,
+                        BorderMode::Reject,
+                        Default::default()
+                    ),
+                    None
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalization_apply() {
+        let normalization = Normalization::FullRange;
+
+        assert_eq!(normalization.apply(200u8), 200.0);
+        assert_eq!(normalization.apply(65535u16), 255.0);
+        assert_eq!(normalization.apply(0.5f32), 127.5);
+        assert_eq!(normalization.apply(2.0f32), 255.0);
+
+        let normalization = Normalization::Range {
+            min: 1000.0,
+            max: 2020.0,
+        };
+
+        assert_eq!(normalization.apply(500u16), 0.0);
+        assert_eq!(normalization.apply(1400u16), 100.0);
+        assert_eq!(normalization.apply(3000u16), 255.0);
+
+        assert_eq!(Normalization::range(1000.0, 2020.0), Some(normalization));
+        assert_eq!(Normalization::range(1000.0, 1000.0), None);
+        assert_eq!(Normalization::range(0.0, f32::INFINITY), None);
+
+        let normalization = Normalization::Range {
+            min: 1000.0,
+            max: 1000.0,
+        };
+
+        assert_eq!(normalization.apply(1000u16), 0.0);
+        assert_eq!(normalization.apply(1001u16), 255.0);
+    }
+
+    #[test]
+    fn test_sample_normalized() {
+        let gray = gradient();
+        let wide = ImageBuffer::<Luma<u16>, _>::from_fn(8, 8, |x, _| Luma([(x * 32 * 257) as u16]));
+        let float =
+            ImageBuffer::<Luma<f32>, _>::from_fn(8, 8, |x, _| Luma([(x * 32) as f32 / 255.0]));
+
+        for interpolation in [Interpolation::Nearest, Interpolation::Bilinear] {
+            for x in [-0.5, 2.0, 2.25, 5.5, 7.75] {
+                let point = Point2::new(x, 4.5);
+                let expected = sample(
+                    &gray,
+                    point,
+                    interpolation,
+                    BorderMode::ZERO,
+                    Default::default(),
+                );
+
+                for value in [
+                    sample(
+                        &wide,
+                        point,
+                        interpolation,
+                        BorderMode::ZERO,
+                        Default::default(),
+                    ),
+                    sample(
+                        &float,
+                        point,
+                        interpolation,
+                        BorderMode::ZERO,
+                        Default::default(),
+                    ),
+                ] {
+                    assert_eq!(value, expected);
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,8 @@
-use std::io::{Error, Read};
+use alloc::vec::Vec;
 
 use nalgebra::Vector2;
 
+use crate::io::{Error, Read};
 use crate::nodes::ThresholdNode;
 
 use super::utils::read_shape;
@@ -55,11 +56,7 @@ impl ShaperTree {
         let mut shifts = Vec::with_capacity(count);
 
         for _ in 0..count {
-            let shift: Vec<Vector2<f32>> = read_shape(reader.by_ref(), size)?
-                .column_iter()
-                .map(|col| Vector2::new(col.x, col.y))
-                .collect();
-            shifts.push(shift);
+            shifts.push(read_shape(reader.by_ref(), size)?);
         }
 
         Ok(shifts)
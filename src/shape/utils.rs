@@ -1,20 +1,25 @@
-use std::io::{Error, Read};
+use alloc::vec::Vec;
 
-use nalgebra::{allocator::Allocator, DefaultAllocator, Dim, Dyn, OMatrix, UninitMatrix, U2};
+use nalgebra::Vector2;
 
-#[inline]
-pub fn read_shape<R: Read>(mut reader: R, size: usize) -> Result<OMatrix<f32, U2, Dyn>, Error>
-where
-    DefaultAllocator: Allocator<U2, Dyn>,
-{
-    let mut m = UninitMatrix::<f32, U2, Dyn>::uninit(U2, Dyn::from_usize(size));
+use crate::io::{Error, Read};
 
+/// Read `size` points stored as `(x, y)` pairs of big-endian `f32`,
+/// which is a column-major `2 x size` matrix.
+#[inline]
+pub fn read_shape<R: Read>(mut reader: R, size: usize) -> Result<Vec<Vector2<f32>>, Error> {
+    let mut shape = Vec::with_capacity(size);
     let mut buf = [0u8; 4];
 
-    for value in m.iter_mut() {
+    for _ in 0..size {
         reader.read_exact(&mut buf)?;
-        value.write(f32::from_be_bytes(buf));
+        let x = f32::from_be_bytes(buf);
+
+        reader.read_exact(&mut buf)?;
+        let y = f32::from_be_bytes(buf);
+
+        shape.push(Vector2::new(x, y));
     }
 
-    Ok(unsafe { m.assume_init() })
+    Ok(shape)
 }
@@ -0,0 +1,316 @@
+use nalgebra::Point2;
+
+use crate::border::BorderMode;
+
+/// Luminance value type of grayscale images.
+///
+/// Values are compared as is by [`Detector`](crate::Detector) and
+/// [`Localizer`](crate::Localizer), so no conversion is needed for inference.
+pub trait Luminance: Copy + PartialOrd {
+    /// Upper bound of the nominal value range, lower bound is zero.
+    const MAX: f32;
+
+    /// Returns the value as float.
+    fn to_f32(self) -> f32;
+
+    /// Returns the value in `[0, 255]` range scaled to the nominal range.
+    fn from_u8(value: u8) -> Self;
+
+    /// Returns the values as bytes if the type is `u8`.
+    #[inline]
+    fn as_bytes(values: &[Self]) -> Option<&[u8]> {
+        let _ = values;
+        None
+    }
+}
+
+impl Luminance for u8 {
+    const MAX: f32 = 255.0;
+
+    #[inline]
+    fn to_f32(self) -> f32 {
+        self as f32
+    }
+
+    #[inline]
+    fn from_u8(value: u8) -> Self {
+        value
+    }
+
+    #[inline]
+    fn as_bytes(values: &[Self]) -> Option<&[u8]> {
+        Some(values)
+    }
+}
+
+impl Luminance for u16 {
+    const MAX: f32 = 65535.0;
+
+    #[inline]
+    fn to_f32(self) -> f32 {
+        self as f32
+    }
+
+    #[inline]
+    fn from_u8(value: u8) -> Self {
+        value as u16 * 257
+    }
+}
+
+impl Luminance for f32 {
+    const MAX: f32 = 1.0;
+
+    #[inline]
+    fn to_f32(self) -> f32 {
+        self
+    }
+
+    #[inline]
+    fn from_u8(value: u8) -> Self {
+        value as f32 / 255.0
+    }
+}
+
+/// Minimal grayscale image interface required for inference.
+///
+/// With `std` feature it is implemented for [`image::ImageBuffer`], flat views and
+/// [`image::SubImage`] with `Luma<u8>`, `Luma<u16>` or `Luma<f32>` pixels, e.g. [`image::GrayImage`].
+/// Other grayscale [`image::GenericImageView`] types can be used through
+/// [`GenericView`](crate::GenericView), raw strided buffers through [`GrayView`](crate::GrayView).
+pub trait GrayImageView {
+    /// Luminance value type.
+    type Luma: Luminance;
+
+    /// Returns `(width, height)` of the image.
+    fn size(&self) -> (u32, u32);
+
+    /// Returns luminance of the pixel at `(x, y)` inside the image bounds.
+    fn luma(&self, x: u32, y: u32) -> Self::Luma;
+
+    /// Returns luminance of the pixel at `(x, y)` without bounds checks.
+    ///
+    /// # Safety
+    ///
+    /// `(x, y)` should be inside the image bounds.
+    #[inline]
+    unsafe fn luma_unchecked(&self, x: u32, y: u32) -> Self::Luma {
+        self.luma(x, y)
+    }
+
+    /// Returns luminance of the pixel at the point or `None` if it is outside the image.
+    #[inline]
+    fn luma_at(&self, point: Point2<i32>) -> Option<Self::Luma> {
+        let (width, height) = self.size();
+        let (x, y) = (point.x as u32, point.y as u32);
+
+        if point.x >= 0 && point.y >= 0 && x < width && y < height {
+            Some(self.luma(x, y))
+        } else {
+            None
+        }
+    }
+
+    /// Returns luminance of the pixel at the point clamped to the image bounds.
+    #[inline]
+    fn luma_clamped(&self, point: Point2<i32>) -> Self::Luma {
+        let (width, height) = self.size();
+        let x = point.x.clamp(0, width as i32 - 1) as u32;
+        let y = point.y.clamp(0, height as i32 - 1) as u32;
+        self.luma(x, y)
+    }
+
+    /// Returns luminance of the pixel at the point handling points outside
+    /// of the image with the border mode, `None` if the window is rejected.
+    #[inline]
+    fn luma_border(&self, point: Point2<i32>, border: BorderMode) -> Option<Self::Luma> {
+        match border {
+            BorderMode::Clamp => Some(self.luma_clamped(point)),
+            BorderMode::Constant(value) => {
+                Some(self.luma_at(point).unwrap_or(Luminance::from_u8(value)))
+            }
+            border => border
+                .map(point, self.size())
+                .map(|point| self.luma(point.x, point.y)),
+        }
+    }
+
+    /// Returns row-major pixels buffer and the distance between rows in pixels
+    /// if the image is stored contiguously, which enables faster batched access.
+    ///
+    /// Pixel at `(x, y)` should be at `y * stride + x` index of the buffer.
+    #[inline]
+    fn buffer(&self) -> Option<(&[Self::Luma], usize)> {
+        None
+    }
+}
+
+/// Implements [`GrayImageView`] methods for images with
+/// [`ExtendedImageView`](pixelutil_image::ExtendedImageView).
+#[cfg(feature = "std")]
+macro_rules! extended_image_view {
+    ($luma:ty) => {
+        type Luma = $luma;
+
+        #[inline]
+        fn size(&self) -> (u32, u32) {
+            image::GenericImageView::dimensions(self)
+        }
+
+        #[inline]
+        fn luma(&self, x: u32, y: u32) -> $luma {
+            image::GenericImageView::get_pixel(self, x, y).0[0]
+        }
+
+        #[inline]
+        fn luma_at(&self, point: Point2<i32>) -> Option<$luma> {
+            pixelutil_image::ExtendedImageView::get_pixel_at(self, point)
+                .map(|image::Luma([value])| value)
+        }
+
+        #[inline]
+        fn luma_clamped(&self, point: Point2<i32>) -> $luma {
+            pixelutil_image::ExtendedImageView::get_pixel_clamped(self, point).0[0]
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+impl<T, C> GrayImageView for image::ImageBuffer<image::Luma<T>, C>
+where
+    T: Luminance + image::Primitive,
+    C: core::ops::Deref<Target = [T]>,
+{
+    extended_image_view!(T);
+
+    #[inline]
+    unsafe fn luma_unchecked(&self, x: u32, y: u32) -> T {
+        let index = y as usize * self.width() as usize + x as usize;
+        *self.as_raw().get_unchecked(index)
+    }
+
+    #[inline]
+    fn buffer(&self) -> Option<(&[T], usize)> {
+        Some((self.as_raw(), self.width() as usize))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, B> GrayImageView for image::flat::View<B, image::Luma<T>>
+where
+    T: Luminance + image::Primitive,
+    B: AsRef<[T]>,
+{
+    extended_image_view!(T);
+}
+
+#[cfg(feature = "std")]
+impl<T, B> GrayImageView for image::flat::ViewMut<B, image::Luma<T>>
+where
+    T: Luminance + image::Primitive,
+    B: AsRef<[T]> + AsMut<[T]>,
+{
+    extended_image_view!(T);
+}
+
+#[cfg(feature = "std")]
+impl<I, T> GrayImageView for image::SubImage<I>
+where
+    I: core::ops::Deref,
+    I::Target: image::GenericImageView<Pixel = image::Luma<T>>,
+    T: Luminance + image::Primitive,
+{
+    type Luma = T;
+
+    #[inline]
+    fn size(&self) -> (u32, u32) {
+        image::GenericImageView::dimensions(&**self)
+    }
+
+    #[inline]
+    fn luma(&self, x: u32, y: u32) -> T {
+        image::GenericImageView::get_pixel(&**self, x, y).0[0]
+    }
+
+    #[inline]
+    unsafe fn luma_unchecked(&self, x: u32, y: u32) -> T {
+        image::GenericImageView::unsafe_get_pixel(&**self, x, y).0[0]
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    struct Gradient;
+
+    impl GrayImageView for Gradient {
+        type Luma = u8;
+
+        fn size(&self) -> (u32, u32) {
+            (4, 3)
+        }
+
+        fn luma(&self, x: u32, y: u32) -> u8 {
+            (10 * y + x) as u8
+        }
+    }
+
+    #[test]
+    fn test_default_luma_access() {
+        let image = image::GrayImage::from_fn(4, 3, |x, y| image::Luma([(10 * y + x) as u8]));
+
+        for x in -2..6 {
+            for y in -2..5 {
+                let point = Point2::new(x, y);
+                assert_eq!(Gradient.luma_at(point), image.luma_at(point));
+                assert_eq!(Gradient.luma_clamped(point), image.luma_clamped(point));
+            }
+        }
+    }
+
+    #[test]
+    fn test_sub_image_luma() {
+        let image = image::GrayImage::from_fn(8, 6, |x, y| image::Luma([(10 * y + x) as u8]));
+        let crop = image::GenericImageView::view(&image, 2, 1, 4, 3);
+
+        assert_eq!(crop.size(), (4, 3));
+
+        for x in -2..6 {
+            for y in -2..5 {
+                let point = Point2::new(x, y);
+                assert_eq!(
+                    crop.luma_at(point),
+                    Gradient.luma_at(point).map(|value| value + 12)
+                );
+                assert_eq!(crop.luma_clamped(point), Gradient.luma_clamped(point) + 12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_luma_border() {
+        let wide = image::ImageBuffer::<image::Luma<u16>, _>::from_fn(4, 3, |x, y| {
+            image::Luma([(10 * y + x) as u16 * 257])
+        });
+        let point = Point2::new(-1, 1);
+
+        assert_eq!(Gradient.luma_border(point, BorderMode::Clamp), Some(10));
+        assert_eq!(
+            Gradient.luma_border(point, BorderMode::Constant(7)),
+            Some(7)
+        );
+        assert_eq!(Gradient.luma_border(point, BorderMode::Reflect), Some(10));
+        assert_eq!(Gradient.luma_border(point, BorderMode::Wrap), Some(13));
+        assert_eq!(Gradient.luma_border(point, BorderMode::Reject), None);
+        assert_eq!(
+            Gradient.luma_border(Point2::new(1, 1), BorderMode::Reject),
+            Some(11)
+        );
+
+        assert_eq!(
+            wide.luma_border(point, BorderMode::Constant(7)),
+            Some(7 * 257)
+        );
+        assert_eq!(wide.luma_border(point, BorderMode::Wrap), Some(13 * 257));
+    }
+}
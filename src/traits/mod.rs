@@ -1,3 +1,5 @@
+pub mod image;
 pub mod region;
 
+pub use self::image::{GrayImageView, Luminance};
 pub use region::Region;
@@ -1,5 +1,7 @@
 use nalgebra::Point2;
 
+use crate::geometry::intersection_over_union;
+
 /// A trait defining a rectangular region with methods to access its properties.
 pub trait Region {
     /// Returns the left coordinate of the region.
@@ -55,4 +57,16 @@ pub trait Region {
     fn square(&self) -> u32 {
         self.width() * self.height()
     }
+
+    /// Returns the intersection over union with the other region
+    /// or `None` if they do not overlap, see [`intersection_over_union`].
+    ///
+    /// Used to group detections while clustering.
+    #[inline]
+    fn overlap(&self, other: &Self) -> Option<f32>
+    where
+        Self: Sized + Copy,
+    {
+        intersection_over_union(*self, *other)
+    }
 }
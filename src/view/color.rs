@@ -0,0 +1,82 @@
+use image::{GenericImageView, Pixel};
+
+use crate::traits::GrayImageView;
+
+use super::LumaCoefficients;
+
+/// Color image viewed as grayscale, e.g. [`image::RgbImage`] or [`image::RgbaImage`],
+/// luminance is computed on each pixel access.
+#[derive(Debug, Clone, Copy)]
+pub struct ColorView<'a, I> {
+    image: &'a I,
+    coefficients: LumaCoefficients,
+}
+
+impl<'a, I> ColorView<'a, I> {
+    /// Create a view over the image with default coefficients.
+    #[inline]
+    pub fn new(image: &'a I) -> Self {
+        Self {
+            image,
+            coefficients: Default::default(),
+        }
+    }
+
+    /// Set coefficients for luminance computation.
+    #[inline]
+    pub fn coefficients(self, value: LumaCoefficients) -> Self {
+        Self {
+            coefficients: value,
+            ..self
+        }
+    }
+}
+
+impl<I, P> GrayImageView for ColorView<'_, I>
+where
+    I: GenericImageView<Pixel = P>,
+    P: Pixel<Subpixel = u8>,
+{
+    type Luma = u8;
+
+    #[inline]
+    fn size(&self) -> (u32, u32) {
+        self.image.dimensions()
+    }
+
+    #[inline]
+    fn luma(&self, x: u32, y: u32) -> u8 {
+        let [r, g, b] = self.image.get_pixel(x, y).to_rgb().0;
+        self.coefficients.luma(r, g, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{DynamicImage, RgbImage, Rgba, RgbaImage};
+
+    use super::*;
+
+    #[test]
+    fn test_color_view_matches_luma8() {
+        let image = RgbImage::from_fn(16, 16, |x, y| {
+            image::Rgb([(x * 16) as u8, (y * 16) as u8, (x * y) as u8])
+        });
+        let gray = DynamicImage::ImageRgb8(image.clone()).into_luma8();
+        let view = ColorView::new(&image);
+
+        assert_eq!(view.size(), gray.dimensions());
+
+        for (x, y, pixel) in gray.enumerate_pixels() {
+            assert!((view.luma(x, y) as i32 - pixel.0[0] as i32).abs() <= 1);
+        }
+    }
+
+    #[test]
+    fn test_color_view_rgba() {
+        let image = RgbaImage::from_pixel(2, 2, Rgba([0, 255, 0, 0]));
+        let view = ColorView::new(&image).coefficients(LumaCoefficients::BT601);
+
+        assert_eq!(view.luma(1, 1), 150);
+    }
+}
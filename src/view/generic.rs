@@ -0,0 +1,93 @@
+use image::{GenericImageView, Luma, Primitive};
+
+use crate::traits::{GrayImageView, Luminance};
+
+/// Any grayscale [`GenericImageView`] viewed as [`GrayImageView`],
+/// e.g. a custom image type implementing the `image` crate traits.
+///
+/// Image buffers, flat views and sub-images implement [`GrayImageView`] directly.
+#[derive(Debug, Clone, Copy)]
+pub struct GenericView<'a, I>(pub &'a I);
+
+impl<I, T> GrayImageView for GenericView<'_, I>
+where
+    I: GenericImageView<Pixel = Luma<T>>,
+    T: Luminance + Primitive,
+{
+    type Luma = T;
+
+    #[inline]
+    fn size(&self) -> (u32, u32) {
+        self.0.dimensions()
+    }
+
+    #[inline]
+    fn luma(&self, x: u32, y: u32) -> T {
+        self.0.get_pixel(x, y).0[0]
+    }
+
+    #[inline]
+    unsafe fn luma_unchecked(&self, x: u32, y: u32) -> T {
+        self.0.unsafe_get_pixel(x, y).0[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::GrayImage;
+    use nalgebra::Point2;
+
+    use crate::detect::model_bytes;
+    use crate::{Detector, Square};
+
+    use super::*;
+
+    /// Image type implemented outside of the `image` crate.
+    struct Checkerboard;
+
+    impl GenericImageView for Checkerboard {
+        type Pixel = Luma<u8>;
+
+        fn dimensions(&self) -> (u32, u32) {
+            (16, 12)
+        }
+
+        fn get_pixel(&self, x: u32, y: u32) -> Luma<u8> {
+            Luma([if (x / 4 + y / 4) & 1 == 0 { 200 } else { 20 }])
+        }
+    }
+
+    #[test]
+    fn test_generic_view_matches_buffer() {
+        let image = GrayImage::from_fn(16, 12, |x, y| Checkerboard.get_pixel(x, y));
+        let view = GenericView(&Checkerboard);
+
+        assert_eq!(view.size(), image.size());
+
+        for x in -2..18 {
+            for y in -2..14 {
+                let point = Point2::new(x, y);
+                assert_eq!(view.luma_at(point), image.luma_at(point));
+                assert_eq!(view.luma_clamped(point), image.luma_clamped(point));
+            }
+        }
+    }
+
+    #[test]
+    fn test_generic_view_classify() {
+        let data = model_bytes(1, [(vec![[0, 0, 0, 64]], vec![1.0, -1.0], -2.0)]);
+        let detector = Detector::load(data.as_slice()).unwrap();
+        let image = GrayImage::from_fn(16, 12, |x, y| Checkerboard.get_pixel(x, y));
+
+        for region in [
+            Square::new(0, 0, 8),
+            Square::new(2, 3, 8),
+            Square::new(-4, 6, 10),
+        ] {
+            assert_eq!(
+                detector.classify(&GenericView(&Checkerboard), region),
+                detector.classify(&image, region)
+            );
+        }
+    }
+}
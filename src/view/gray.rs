@@ -0,0 +1,143 @@
+use crate::traits::GrayImageView;
+
+use super::{validate, ViewError};
+
+/// Borrowed grayscale buffer with rows separated by `stride` bytes,
+/// e.g. Y plane of NV12 or I420 camera frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrayView<'a> {
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+    stride: usize,
+}
+
+impl<'a> GrayView<'a> {
+    /// Create a view over the strided grayscale buffer.
+    ///
+    /// ### Arguments
+    ///
+    /// * `data` -- pixels buffer, the last row may be shorter than `stride`;
+    /// * `width` -- image width in pixels;
+    /// * `height` -- image height in pixels;
+    /// * `stride` -- distance between rows in bytes.
+    #[inline]
+    pub fn new(data: &'a [u8], width: u32, height: u32, stride: usize) -> Result<Self, ViewError> {
+        validate(data.len(), width as usize, height, stride)?;
+
+        Ok(Self {
+            data,
+            width,
+            height,
+            stride,
+        })
+    }
+
+    /// Create a view over the tightly packed grayscale buffer.
+    #[inline]
+    pub fn packed(data: &'a [u8], width: u32, height: u32) -> Result<Self, ViewError> {
+        Self::new(data, width, height, width as usize)
+    }
+
+    /// Returns the underlying buffer.
+    #[inline]
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns the image width in pixels.
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the image height in pixels.
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the distance between rows in bytes.
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Returns the row of pixels at `y`.
+    #[inline]
+    pub fn row(&self, y: u32) -> &'a [u8] {
+        let start = y as usize * self.stride;
+        &self.data[start..start + self.width as usize]
+    }
+}
+
+impl GrayImageView for GrayView<'_> {
+    type Luma = u8;
+
+    #[inline]
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    #[inline]
+    fn luma(&self, x: u32, y: u32) -> u8 {
+        self.data[y as usize * self.stride + x as usize]
+    }
+
+    #[inline]
+    unsafe fn luma_unchecked(&self, x: u32, y: u32) -> u8 {
+        *self
+            .data
+            .get_unchecked(y as usize * self.stride + x as usize)
+    }
+
+    #[inline]
+    fn buffer(&self) -> Option<(&[u8], usize)> {
+        Some((self.data, self.stride))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Point2;
+
+    use super::*;
+
+    #[test]
+    fn test_gray_view_strided_access() {
+        let data: [u8; 13] = [0, 1, 2, 9, 9, 10, 11, 12, 9, 9, 20, 21, 22];
+        let view = GrayView {
+            data: &data[..],
+            width: 3,
+            height: 3,
+            stride: 5,
+        };
+
+        assert_eq!(view.size(), (3, 3));
+        assert_eq!(view.row(1), &[10, 11, 12]);
+        assert_eq!(view.luma(2, 1), 12);
+        assert_eq!(view.luma_at(Point2::new(3, 0)), None);
+        assert_eq!(view.luma_clamped(Point2::new(5, -1)), 2);
+        assert_eq!(view.luma_clamped(Point2::new(-1, 4)), 20);
+    }
+
+    #[test]
+    fn test_gray_view_new() {
+        let data = [0u8; 13];
+
+        assert!(GrayView::new(&data, 3, 3, 5).is_ok());
+        assert!(GrayView::packed(&data[..9], 3, 3).is_ok());
+        assert_eq!(GrayView::new(&data, 0, 3, 5), Err(ViewError::EmptyImage));
+        assert_eq!(
+            GrayView::new(&data, 3, 3, 2),
+            Err(ViewError::StrideTooSmall(3))
+        );
+        assert_eq!(
+            GrayView::new(&data[..12], 3, 3, 5),
+            Err(ViewError::BufferTooSmall {
+                expected: 13,
+                actual: 12
+            })
+        );
+    }
+}
@@ -0,0 +1,82 @@
+/// Weights of red, green and blue channels for luminance computation.
+///
+/// Weights are stored in 16-bit fixed point, so luminance is computed
+/// with integer arithmetic only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LumaCoefficients {
+    weights: [u32; 3],
+}
+
+impl LumaCoefficients {
+    /// ITU-R BT.601 coefficients `(0.299, 0.587, 0.114)`.
+    pub const BT601: Self = Self {
+        weights: [19595, 38470, 7471],
+    };
+
+    /// ITU-R BT.709 coefficients `(0.2126, 0.7152, 0.0722)`,
+    /// the same as used by `image` crate conversion to grayscale.
+    pub const BT709: Self = Self {
+        weights: [13933, 46871, 4732],
+    };
+
+    /// Create coefficients from channel weights.
+    ///
+    /// ### Arguments
+    ///
+    /// * `r` -- red channel weight in `[0, 1]` range;
+    /// * `g` -- green channel weight in `[0, 1]` range;
+    /// * `b` -- blue channel weight in `[0, 1]` range.
+    #[inline]
+    pub fn new(r: f32, g: f32, b: f32) -> Self {
+        let weight = |value: f32| (value.clamp(0.0, 1.0) * 65536.0 + 0.5) as u32;
+
+        Self {
+            weights: [weight(r), weight(g), weight(b)],
+        }
+    }
+
+    /// Compute luminance of the color.
+    #[inline]
+    pub fn luma(&self, r: u8, g: u8, b: u8) -> u8 {
+        let [wr, wg, wb] = self.weights;
+        let value = (wr * r as u32 + wg * g as u32 + wb * b as u32 + 32768) >> 16;
+        value.min(255) as u8
+    }
+}
+
+impl Default for LumaCoefficients {
+    /// Create default ITU-R BT.709 coefficients.
+    #[inline]
+    fn default() -> Self {
+        Self::BT709
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_luma_coefficients_constants() {
+        assert_eq!(
+            LumaCoefficients::new(0.299, 0.587, 0.114),
+            LumaCoefficients::BT601
+        );
+        assert_eq!(
+            LumaCoefficients::new(0.2126, 0.7152, 0.0722),
+            LumaCoefficients::BT709
+        );
+    }
+
+    #[test]
+    fn test_luma_coefficients_luma() {
+        for coefficients in [LumaCoefficients::BT601, LumaCoefficients::BT709] {
+            assert_eq!(coefficients.luma(0, 0, 0), 0);
+            assert_eq!(coefficients.luma(255, 255, 255), 255);
+            assert_eq!(coefficients.luma(100, 100, 100), 100);
+        }
+
+        assert_eq!(LumaCoefficients::BT601.luma(255, 0, 0), 76);
+        assert_eq!(LumaCoefficients::new(1.0, 1.0, 0.0).luma(200, 200, 0), 255);
+    }
+}
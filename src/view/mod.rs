@@ -0,0 +1,55 @@
+#[cfg(feature = "std")]
+mod color;
+#[cfg(feature = "std")]
+mod generic;
+mod gray;
+mod luma;
+mod packed;
+mod yuv;
+
+use thiserror::Error;
+
+#[cfg(feature = "std")]
+pub use color::ColorView;
+#[cfg(feature = "std")]
+pub use generic::GenericView;
+pub use gray::GrayView;
+pub use luma::LumaCoefficients;
+pub use packed::{PackedFormat, PackedView};
+pub use yuv::{YuvFormat, YuvView};
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum ViewError {
+    #[error("`width` and `height` should be non zero")]
+    EmptyImage,
+    #[error("`stride` should be not less than row size of {0} bytes")]
+    StrideTooSmall(usize),
+    #[error("buffer of {actual} bytes is too small, expected at least {expected}")]
+    BufferTooSmall { expected: usize, actual: usize },
+}
+
+/// Check that the buffer of `len` bytes holds `height` rows of `row` bytes
+/// separated by `stride` bytes.
+fn validate(len: usize, row: usize, height: u32, stride: usize) -> Result<(), ViewError> {
+    if row == 0 || height == 0 {
+        return Err(ViewError::EmptyImage);
+    }
+
+    if stride < row {
+        return Err(ViewError::StrideTooSmall(row));
+    }
+
+    let expected = (height as usize - 1)
+        .checked_mul(stride)
+        .and_then(|len| len.checked_add(row))
+        .unwrap_or(usize::MAX);
+
+    if len < expected {
+        Err(ViewError::BufferTooSmall {
+            expected,
+            actual: len,
+        })
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use crate::traits::GrayImageView;
+
+use super::{validate, LumaCoefficients, ViewError};
+
+/// Channels layout of the interleaved 8-bit color buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackedFormat {
+    Rgb,
+    Rgba,
+    Bgr,
+    Bgra,
+}
+
+impl PackedFormat {
+    /// Returns number of bytes per pixel.
+    #[inline]
+    pub fn channels(self) -> usize {
+        match self {
+            Self::Rgb | Self::Bgr => 3,
+            Self::Rgba | Self::Bgra => 4,
+        }
+    }
+
+    #[inline]
+    fn rgb(self, pixel: &[u8]) -> (u8, u8, u8) {
+        match self {
+            Self::Rgb | Self::Rgba => (pixel[0], pixel[1], pixel[2]),
+            Self::Bgr | Self::Bgra => (pixel[2], pixel[1], pixel[0]),
+        }
+    }
+}
+
+/// Borrowed interleaved color buffer viewed as grayscale,
+/// luminance is computed on each pixel access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackedView<'a> {
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+    stride: usize,
+    format: PackedFormat,
+    coefficients: LumaCoefficients,
+}
+
+impl<'a> PackedView<'a> {
+    /// Create a view over the strided color buffer with default coefficients.
+    ///
+    /// ### Arguments
+    ///
+    /// * `data` -- pixels buffer, the last row may be shorter than `stride`;
+    /// * `width` -- image width in pixels;
+    /// * `height` -- image height in pixels;
+    /// * `stride` -- distance between rows in bytes;
+    /// * `format` -- channels layout.
+    #[inline]
+    pub fn new(
+        data: &'a [u8],
+        width: u32,
+        height: u32,
+        stride: usize,
+        format: PackedFormat,
+    ) -> Result<Self, ViewError> {
+        let row = (width as usize).saturating_mul(format.channels());
+        validate(data.len(), row, height, stride)?;
+
+        Ok(Self {
+            data,
+            width,
+            height,
+            stride,
+            format,
+            coefficients: Default::default(),
+        })
+    }
+
+    /// Create a view over the tightly packed color buffer with default coefficients.
+    #[inline]
+    pub fn packed(
+        data: &'a [u8],
+        width: u32,
+        height: u32,
+        format: PackedFormat,
+    ) -> Result<Self, ViewError> {
+        Self::new(
+            data,
+            width,
+            height,
+            width as usize * format.channels(),
+            format,
+        )
+    }
+
+    /// Set coefficients for luminance computation.
+    #[inline]
+    pub fn coefficients(self, value: LumaCoefficients) -> Self {
+        Self {
+            coefficients: value,
+            ..self
+        }
+    }
+
+    /// Returns the channels layout.
+    #[inline]
+    pub fn format(&self) -> PackedFormat {
+        self.format
+    }
+}
+
+impl GrayImageView for PackedView<'_> {
+    type Luma = u8;
+
+    #[inline]
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    #[inline]
+    fn luma(&self, x: u32, y: u32) -> u8 {
+        let start = y as usize * self.stride + x as usize * self.format.channels();
+        let (r, g, b) = self.format.rgb(&self.data[start..]);
+        self.coefficients.luma(r, g, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_packed_view_formats() {
+        let rgb = [10, 20, 30, 200, 100, 0, 0, 0, 255, 255, 255, 255];
+        let bgr = [30, 20, 10, 0, 100, 200, 255, 0, 0, 255, 255, 255];
+        let rgba = [
+            10, 20, 30, 0, 200, 100, 0, 0, 0, 0, 255, 0, 255, 255, 255, 0,
+        ];
+
+        let rgb = PackedView::packed(&rgb, 2, 2, PackedFormat::Rgb).unwrap();
+        let bgr = PackedView::packed(&bgr, 2, 2, PackedFormat::Bgr).unwrap();
+        let rgba = PackedView::packed(&rgba, 2, 2, PackedFormat::Rgba).unwrap();
+
+        for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+            assert_eq!(rgb.luma(x, y), bgr.luma(x, y));
+            assert_eq!(rgb.luma(x, y), rgba.luma(x, y));
+        }
+
+        assert_eq!(rgb.luma(1, 1), 255);
+        assert_eq!(
+            rgb.coefficients(LumaCoefficients::new(1.0, 0.0, 0.0))
+                .luma(1, 0),
+            200
+        );
+    }
+
+    #[test]
+    fn test_packed_view_strided() {
+        let data = [0, 0, 0, 9, 9, 60, 60, 60];
+        let view = PackedView::new(&data, 1, 2, 5, PackedFormat::Bgr).unwrap();
+
+        assert_eq!(view.size(), (1, 2));
+        assert_eq!(view.luma(0, 1), 60);
+        assert_eq!(
+            PackedView::new(&data, 2, 2, 5, PackedFormat::Bgr),
+            Err(ViewError::StrideTooSmall(6))
+        );
+        assert_eq!(
+            PackedView::new(&data[..7], 1, 2, 5, PackedFormat::Bgr),
+            Err(ViewError::BufferTooSmall {
+                expected: 8,
+                actual: 7
+            })
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use crate::traits::GrayImageView;
+
+use super::{validate, ViewError};
+
+/// Layout of the 8-bit YUV buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YuvFormat {
+    /// Y plane followed by interleaved UV plane.
+    Nv12,
+    /// Y plane followed by U and V planes.
+    I420,
+    /// Interleaved `Y0 U Y1 V` macropixels.
+    Yuyv,
+}
+
+impl YuvFormat {
+    /// Returns number of bytes between luminance samples in a row.
+    #[inline]
+    fn step(self) -> usize {
+        match self {
+            Self::Nv12 | Self::I420 => 1,
+            Self::Yuyv => 2,
+        }
+    }
+}
+
+/// Borrowed YUV frame viewed as grayscale, only luminance samples are accessed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YuvView<'a> {
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+    stride: usize,
+    format: YuvFormat,
+}
+
+impl<'a> YuvView<'a> {
+    /// Create a view over the YUV frame.
+    ///
+    /// ### Arguments
+    ///
+    /// * `data` -- frame buffer starting with luminance samples,
+    ///   chroma planes of planar formats may be omitted;
+    /// * `width` -- image width in pixels;
+    /// * `height` -- image height in pixels;
+    /// * `stride` -- distance between rows of luminance (or packed) samples in bytes;
+    /// * `format` -- frame layout.
+    #[inline]
+    pub fn new(
+        data: &'a [u8],
+        width: u32,
+        height: u32,
+        stride: usize,
+        format: YuvFormat,
+    ) -> Result<Self, ViewError> {
+        let row = (width as usize).saturating_mul(format.step());
+        validate(data.len(), row, height, stride)?;
+
+        Ok(Self {
+            data,
+            width,
+            height,
+            stride,
+            format,
+        })
+    }
+
+    /// Returns the frame layout.
+    #[inline]
+    pub fn format(&self) -> YuvFormat {
+        self.format
+    }
+}
+
+impl GrayImageView for YuvView<'_> {
+    type Luma = u8;
+
+    #[inline]
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    #[inline]
+    fn luma(&self, x: u32, y: u32) -> u8 {
+        self.data[y as usize * self.stride + x as usize * self.format.step()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yuv_view_planar() {
+        // 2x2 frame with Y stride 3, followed by 2 bytes of UV.
+        let data = [1, 2, 0, 3, 4, 0, 128, 128];
+
+        for format in [YuvFormat::Nv12, YuvFormat::I420] {
+            let view = YuvView::new(&data, 2, 2, 3, format).unwrap();
+            assert_eq!(view.luma(0, 0), 1);
+            assert_eq!(view.luma(1, 1), 4);
+        }
+    }
+
+    #[test]
+    fn test_yuv_view_yuyv() {
+        let data = [1, 128, 2, 128, 3, 128, 4, 128];
+        let view = YuvView::new(&data, 2, 2, 4, YuvFormat::Yuyv).unwrap();
+
+        assert_eq!(view.luma(1, 0), 2);
+        assert_eq!(view.luma(0, 1), 3);
+        assert_eq!(
+            YuvView::new(&data, 2, 2, 3, YuvFormat::Yuyv),
+            Err(ViewError::StrideTooSmall(4))
+        );
+    }
+}
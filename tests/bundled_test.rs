@@ -0,0 +1,47 @@
+#![cfg(feature = "bundled-models")]
+
+mod common;
+
+use image::GrayImage;
+use rstest::rstest;
+
+use nalgebra::Point2;
+
+use pico_detect::{Detector, Localizer, Shaper, Square};
+
+use common::{classify_case, detector, localize_case, localizer, shaper, shaper_case};
+
+#[rstest]
+fn test_bundled_detector(detector: Detector, classify_case: (GrayImage, Square, Option<f32>)) {
+    let (image, region, _) = classify_case;
+
+    assert_eq!(
+        Detector::face().classify(&image, region),
+        detector.classify(&image, region)
+    );
+}
+
+#[rstest]
+fn test_bundled_localizer(
+    localizer: Localizer,
+    localize_case: (GrayImage, [(Square, Point2<f32>); 2]),
+) {
+    let (image, tests) = localize_case;
+
+    for (region, _) in tests.iter() {
+        assert_eq!(
+            Localizer::pupil().localize(&image, region.to_owned().into()),
+            localizer.localize(&image, region.to_owned().into())
+        );
+    }
+}
+
+#[rstest]
+fn test_bundled_shaper(shaper: Shaper, shaper_case: (GrayImage, Square, Vec<Point2<f32>>)) {
+    let (image, region, _) = shaper_case;
+
+    assert_eq!(
+        Shaper::face5().shape(&image, region),
+        shaper.shape(&image, region)
+    );
+}
@@ -76,7 +76,7 @@ pub fn localize_case(test_image: GrayImage) -> (GrayImage, [(Square, Point2<f32>
             ),
             (
                 Square::at(259, 259).of_size(15),
-                Point2::new(266.5190, 267.5272),
+                Point2::new(266.519, 267.5272),
             ),
         ],
     )
@@ -1,3 +1,5 @@
+#![cfg(feature = "std")]
+
 mod common;
 
 use approx::assert_abs_diff_eq;
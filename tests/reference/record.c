@@ -0,0 +1,115 @@
+/*
+ * Records detections of the original pico C implementation for `tests/reference_test.rs`.
+ *
+ * Build with `picornt.c` of pico where `find_objects` runs `run_cascade`:
+ *
+ *     cc -O2 -I<pico>/rnt -o record record.c
+ *     ./record cascade.bin image.pgm find > find_objects.txt
+ *     ./record cascade.bin image.pgm cluster > cluster_detections.txt
+ */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "picornt.c"
+
+#define MAXNDETECTIONS 4096
+
+/* parameters of `find_objects`, same as in `tests/reference_test.rs` */
+#define MINSIZE 24.0f
+#define MAXSIZE 96.0f
+#define SCALEFACTOR 1.1f
+#define STRIDEFACTOR 0.1f
+
+static void* read_file(const char* path)
+{
+	FILE* file;
+	long size;
+	void* data;
+
+	file = fopen(path, "rb");
+
+	if(!file)
+		return 0;
+
+	fseek(file, 0, SEEK_END);
+	size = ftell(file);
+	fseek(file, 0, SEEK_SET);
+
+	data = malloc(size);
+
+	if(fread(data, 1, size, file) != (size_t)size)
+	{
+		free(data);
+		data = 0;
+	}
+
+	fclose(file);
+
+	return data;
+}
+
+/* binary PGM with the maximum value of 255 and a single space after the header fields */
+static unsigned char* read_pgm(const char* path, int* nrows, int* ncols)
+{
+	FILE* file;
+	unsigned char* pixels;
+	int maxval;
+
+	file = fopen(path, "rb");
+
+	if(!file)
+		return 0;
+
+	if(fscanf(file, "P5 %d %d %d", ncols, nrows, &maxval) != 3 || maxval != 255 || fgetc(file) == EOF)
+	{
+		fclose(file);
+		return 0;
+	}
+
+	pixels = (unsigned char*)malloc(*nrows**ncols);
+
+	if(fread(pixels, 1, *nrows**ncols, file) != (size_t)(*nrows**ncols))
+	{
+		free(pixels);
+		pixels = 0;
+	}
+
+	fclose(file);
+
+	return pixels;
+}
+
+int main(int argc, char* argv[])
+{
+	static float rcsq[4*MAXNDETECTIONS];
+	void* cascade;
+	unsigned char* pixels;
+	int nrows, ncols, ndetections, i;
+
+	if(argc != 4 || (strcmp(argv[3], "find") && strcmp(argv[3], "cluster")))
+	{
+		fprintf(stderr, "usage: %s <cascade> <image.pgm> find|cluster\n", argv[0]);
+		return 1;
+	}
+
+	cascade = read_file(argv[1]);
+	pixels = read_pgm(argv[2], &nrows, &ncols);
+
+	if(!cascade || !pixels)
+	{
+		fprintf(stderr, "cannot read inputs\n");
+		return 1;
+	}
+
+	ndetections = find_objects(rcsq, MAXNDETECTIONS, cascade, pixels, nrows, ncols, ncols, SCALEFACTOR, STRIDEFACTOR, MINSIZE, MAXSIZE);
+
+	if(!strcmp(argv[3], "cluster"))
+		ndetections = cluster_detections(rcsq, ndetections);
+
+	for(i=0; i<ndetections; ++i)
+		printf("%.9g %.9g %.9g %.9g\n", rcsq[4*i+0], rcsq[4*i+1], rcsq[4*i+2], rcsq[4*i+3]);
+
+	return 0;
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "std")]
+
+use std::{fs, path::Path};
+
+use image::GrayImage;
+use rstest::{fixture, rstest};
+
+use pico_detect::{reference::ReferenceDetect, Detection, Detector, Target};
+
+/// Parameters used to record `tests/reference/*.txt` with `tests/reference/record.c`.
+const PARAMETERS: ReferenceDetect = ReferenceDetect {
+    min_size: 24.0,
+    max_size: 96.0,
+    scale_factor: 1.1,
+    stride_factor: 0.1,
+};
+
+#[fixture]
+fn reference_detector() -> Detector {
+    Detector::load(
+        fs::read("./tests/reference/cascade.bin")
+            .unwrap()
+            .as_slice(),
+    )
+    .unwrap()
+}
+
+#[fixture]
+fn reference_image() -> GrayImage {
+    image::open("./tests/reference/image.pgm")
+        .unwrap()
+        .to_luma8()
+}
+
+/// Read detections recorded by the original pico C implementation,
+/// each line has `row col size score` printed with `printf("%.9g %.9g %.9g %.9g\n", ...)`.
+fn recorded(path: impl AsRef<Path>) -> Vec<[u32; 4]> {
+    fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let values: Vec<f32> = line
+                .split_whitespace()
+                .map(|value| value.parse().unwrap())
+                .collect();
+            [values[0], values[1], values[2], values[3]].map(f32::to_bits)
+        })
+        .collect()
+}
+
+fn bits(detections: &[Detection<Target>]) -> Vec<[u32; 4]> {
+    detections
+        .iter()
+        .map(|detection| {
+            let region = detection.region();
+            [region.y(), region.x(), region.size(), detection.score()].map(f32::to_bits)
+        })
+        .collect()
+}
+
+#[rstest]
+fn test_reference_find_recorded(reference_detector: Detector, reference_image: GrayImage) {
+    let expected = recorded("./tests/reference/find_objects.txt");
+    assert!(!expected.is_empty());
+    assert_eq!(
+        bits(&PARAMETERS.find(&reference_detector, &reference_image)),
+        expected
+    );
+}
+
+#[rstest]
+fn test_reference_detect_recorded(reference_detector: Detector, reference_image: GrayImage) {
+    let expected = recorded("./tests/reference/cluster_detections.txt");
+    assert!(!expected.is_empty());
+    assert_eq!(
+        bits(&PARAMETERS.run(&reference_detector, &reference_image)),
+        expected
+    );
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "std")]
+
 mod common;
 
 use approx::assert_abs_diff_eq;
@@ -14,11 +16,7 @@ use common::{shaper, shaper_case};
 fn test_shaper_predict(shaper: Shaper, shaper_case: (GrayImage, Square, Vec<Point2<f32>>)) {
     let (image, region, points) = shaper_case;
 
-    for (p1, p2) in shaper
-        .shape(&image, region.into())
-        .iter()
-        .zip(points.iter())
-    {
+    for (p1, p2) in shaper.shape(&image, region).iter().zip(points.iter()) {
         assert_abs_diff_eq!(*p1, *p2, epsilon = 1e-4);
     }
 }